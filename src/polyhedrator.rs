@@ -1,15 +1,28 @@
 mod builder;
+mod conway_operator;
+pub mod counts;
+mod goldberg_coxeter;
 mod keys;
+pub mod notation;
 pub mod operators;
+pub mod progress;
 pub mod seeds;
+pub mod simplify;
 
-pub use operators::Operator;
+pub use builder::Builder;
+pub use conway_operator::{ConwayOperator, CustomOperator};
+pub use counts::{Counts, SizeError, SizeLimit};
+pub use keys::{FaceKey, VertexKey};
+pub use notation::{
+    BuildOptions, Dialect, Notation, NotationContext, NotationError, PrintOptions, Syntax,
+};
+pub use operators::{Chirality, EdgeSelector, FaceSelector, Operator, VertexSelector};
+pub use progress::{BuildError, CancellationToken, Observer};
 pub type Vertex = Point3<f64>;
 
-use builder::Builder;
-use cgmath::{EuclideanSpace, InnerSpace, Point3, Vector3};
+use cgmath::{EuclideanSpace, InnerSpace, Point3, Vector3, Zero};
 use fnv::FnvHashMap;
-use keys::{FaceKey, VertexKey};
+use progress::Progress;
 
 #[derive(Clone, Debug)]
 pub struct Face {
@@ -32,6 +45,8 @@ impl Face {
 pub struct Polyhedron {
     vertices: Vec<Vertex>,
     faces: Vec<Face>,
+    chirality: Option<Chirality>,
+    seed_faces: Vec<Option<u32>>,
 }
 
 impl Polyhedron {
@@ -43,6 +58,73 @@ impl Polyhedron {
         &self.vertices
     }
 
+    /// Returns the handedness of the first chiral operator applied to this polyhedron, or `None`
+    /// if no chiral operator has been applied.
+    pub fn chirality(&self) -> Option<Chirality> {
+        self.chirality
+    }
+
+    /// Returns, for each face, the index of the seed face it descends from, or `None` if it was
+    /// created from a vertex or edge.
+    pub fn seed_faces(&self) -> &[Option<u32>] {
+        &self.seed_faces
+    }
+
+    /// Returns, for each face, whether it matches the selector.
+    pub fn select_faces(&self, selector: &FaceSelector) -> Vec<bool> {
+        let classes = selector.class().map(|_| self.classify_faces());
+        (0..self.faces.len())
+            .map(|i| {
+                let face = &self.faces[i];
+                let side_count = selector.side_count();
+                if side_count != 0 && side_count as usize != face.indices.len() {
+                    return false;
+                }
+                if let (Some(class), Some(classes)) = (selector.class(), &classes) {
+                    if class as usize != classes[i] {
+                        return false;
+                    }
+                }
+                if let Some(indices) = selector.indices() {
+                    if indices.binary_search(&(i as u32)).is_err() {
+                        return false;
+                    }
+                }
+                if let Some(seed_faces) = selector.seed_faces() {
+                    match self.seed_faces[i] {
+                        Some(seed_face) if seed_faces.binary_search(&seed_face).is_ok() => {},
+                        _ => return false,
+                    }
+                }
+                let (minimum, maximum) = selector.area();
+                if minimum.is_some() || maximum.is_some() {
+                    let area = area(self.face_vertices(face));
+                    let is_too_small = matches!(minimum, Some(minimum) if area < minimum);
+                    let is_too_large = matches!(maximum, Some(maximum) if area > maximum);
+                    if is_too_small || is_too_large {
+                        return false;
+                    }
+                }
+                if let Some((direction, angle)) = selector.normal() {
+                    let cosine = normal(self.face_vertices(face)).dot(direction.normalize());
+                    if cosine < angle.to_radians().cos() {
+                        return false;
+                    }
+                }
+                true
+            })
+            .collect()
+    }
+
+    /// Returns, for each vertex, whether it matches the selector.
+    pub fn select_vertices(&self, selector: VertexSelector) -> Vec<bool> {
+        let degree = selector.degree();
+        self.vertex_degrees()
+            .into_iter()
+            .map(|vertex_degree| degree == 0 || degree == vertex_degree)
+            .collect()
+    }
+
     pub fn classify_faces(&self) -> Vec<usize> {
         let mut face_classes = Vec::new();
         let mut classes = FnvHashMap::default();
@@ -53,8 +135,6 @@ impl Polyhedron {
             let class = classes.entry(sig).or_insert(new_index);
             face_classes.push(*class);
         }
-
-        eprintln!("Face class count: {}", classes.len());
         face_classes
     }
 
@@ -100,12 +180,44 @@ impl Polyhedron {
 
     /// Applies the operator and returns the resulting polyhedron.
     pub fn apply(self, operator: Operator) -> Polyhedron {
+        self.apply_with_progress(operator, &Progress::default())
+    }
+
+    /// Applies the operator, reporting each face assembled and stopping early once the build is
+    /// cancelled. A cancelled build returns whatever it had assembled, which is to be discarded.
+    fn apply_with_progress(self, operator: Operator, progress: &Progress) -> Polyhedron {
         use operators::*;
 
         match operator {
-            Operator::Ambo => self.ambo(),
-            Operator::Dual => self.dual(),
-            Operator::Kis(kis) => self.kis(kis),
+            Operator::Ambo => self.build_ambo(progress),
+            Operator::Dual => self.build_dual(progress),
+            Operator::Kis(kis) => self.build_kis(kis, progress),
+            Operator::Truncate(truncate) => self.build_truncate(truncate, progress),
+            Operator::Join => self.build_join(progress),
+            Operator::Ortho => self.build_ortho(progress),
+            Operator::Expand => self.build_expand(progress),
+            Operator::Gyro(chirality) => self.build_gyro(chirality, progress),
+            Operator::Snub(chirality) => self.build_snub(chirality, progress),
+            Operator::Bevel => self.build_bevel(progress),
+            Operator::Meta => self.build_meta(progress),
+            Operator::Needle(needle) => self.build_needle(needle, progress),
+            Operator::Zip(zip) => self.build_zip(zip, progress),
+            Operator::Propeller(chirality) => self.build_propeller(chirality, progress),
+            Operator::Reflect => self.reflect(),
+            Operator::Chamfer(chamfer) => self.build_chamfer(chamfer, progress),
+            Operator::Whirl(whirl) => self.build_whirl(whirl, progress),
+            Operator::Loft(loft) => self.build_loft(loft, progress),
+            Operator::Lace(lace) => self.build_lace(lace, progress),
+            Operator::Stake(stake) => self.build_stake(stake, progress),
+            Operator::Quinto(quinto) => self.build_quinto(quinto, progress),
+            Operator::JoinedLace(joined_lace) => self.build_joined_lace(joined_lace, progress),
+            Operator::Cross(cross) => self.build_cross(cross, progress),
+            Operator::GoldbergCoxeter(goldberg_coxeter) => {
+                self.build_goldberg_coxeter(goldberg_coxeter, progress)
+            },
+            Operator::Inset(inset) => self.build_inset(inset, progress),
+            Operator::Extrude(extrude) => self.build_extrude(extrude, progress),
+            Operator::Custom(custom) => self.build_conway(custom.operator(), progress),
         }
     }
 
@@ -118,122 +230,1143 @@ impl Polyhedron {
         polyhedron
     }
 
+    /// Applies each operator in order like [`Polyhedron::apply_iter`], or returns an error if the
+    /// result is predicted to exceed the limit. Nothing is built if the limit would be exceeded.
+    pub fn apply_iter_with_limit(
+        self,
+        operators: impl IntoIterator<Item = Operator>,
+        limit: SizeLimit,
+    ) -> Result<Polyhedron, SizeError> {
+        let operators: Vec<Operator> = operators.into_iter().collect();
+        limit.check(&Counts::of(&self).apply_iter(&operators))?;
+        Ok(self.apply_iter(operators))
+    }
+
+    /// Applies each operator in order like [`Polyhedron::apply_iter_with_limit`], reporting the
+    /// progress of the build to the observer. Returns [`BuildError::Cancelled`] once the token is
+    /// cancelled, abandoning the operator being applied.
+    pub fn apply_iter_observed(
+        self,
+        operators: impl IntoIterator<Item = Operator>,
+        limit: SizeLimit,
+        observer: impl Observer + 'static,
+        token: &CancellationToken,
+    ) -> Result<Polyhedron, BuildError> {
+        let operators: Vec<Operator> = operators.into_iter().collect();
+        limit.check(&Counts::of(&self).apply_iter(&operators))?;
+
+        let progress = Progress::new(observer, token.clone());
+        let mut polyhedron = self;
+        for (index, op) in operators.into_iter().enumerate() {
+            if progress.is_cancelled() {
+                return Err(BuildError::Cancelled);
+            }
+            progress.operator_started(index, &op);
+            polyhedron = polyhedron.apply_with_progress(op.clone(), &progress);
+            if progress.is_cancelled() {
+                return Err(BuildError::Cancelled);
+            }
+            progress.operator_finished(index, &op, &polyhedron);
+        }
+        Ok(polyhedron)
+    }
+
+    /// Applies an operator through its [`ConwayOperator`] implementation, and returns the
+    /// resulting polyhedron.
+    pub fn apply_conway(self, operator: &dyn ConwayOperator) -> Polyhedron {
+        self.build_conway(operator, &Progress::default())
+    }
+
+    fn build_conway(self, operator: &dyn ConwayOperator, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        operator.apply(&self, &mut builder);
+        let chirality = self.chirality.or_else(|| operator.chirality());
+        builder.build_polyhedron(chirality, &self.seed_faces)
+    }
+
     /// Applies the `kis` operator and returns the resulting polyhedron.
     pub fn kis(self, kis: operators::Kis) -> Polyhedron {
-        let mut builder = Builder::new();
+        self.build_kis(kis, &Progress::default())
+    }
+
+    fn build_kis(self, kis: operators::Kis, progress: &Progress) -> Polyhedron {
+        self.build_conway(&kis, progress)
+    }
+
+    /// Applies the `dual` operator and returns the resulting polyhedron.
+    pub fn dual(self) -> Polyhedron {
+        self.build_dual(&Progress::default())
+    }
+
+    fn build_dual(self, progress: &Progress) -> Polyhedron {
+        self.build_conway(&operators::Dual, progress)
+    }
+
+    /// Applies the `ambo` operator and returns the resulting polyhedron.
+    pub fn ambo(self) -> Polyhedron {
+        self.build_ambo(&Progress::default())
+    }
+
+    fn build_ambo(self, progress: &Progress) -> Polyhedron {
+        self.build_conway(&operators::Ambo, progress)
+    }
+
+    /// Applies the `truncate` operator and returns the resulting polyhedron.
+    pub fn truncate(self, truncate: operators::Truncate) -> Polyhedron {
+        self.build_truncate(truncate, &Progress::default())
+    }
+
+    fn build_truncate(self, truncate: operators::Truncate, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        let selected = self.select_vertices(truncate.selector());
+        let is_cut = |vertex: u32| -> bool { selected[vertex as usize] };
 
         for i in 0..self.vertices.len() {
-            let vertex = self.vertices[i];
-            builder.add_vertex(VertexKey::Seed(i as u32), vertex);
+            if !is_cut(i as u32) {
+                builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+            }
         }
 
-        for face_index in 0..self.faces.len() {
-            let face = &self.faces[face_index];
-            let face_index = face_index as u32;
-            let is_identity =
-                kis.side_count() != 0 && kis.side_count() as usize != face.indices.len();
-            let mut v1_key = VertexKey::Seed(*face.indices.last().unwrap());
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let (mut v1, mut v2) = face.last();
 
-            let apex_position = {
-                let vertices = self.face_vertices(face);
-                let center = center(vertices.clone());
-                let normal = normal(vertices.clone());
-                let dist_to_center = mean_distance(vertices.clone(), center);
-                center + normal * (kis.apex_scale() * dist_to_center)
-            };
+            let mut ring = Vec::with_capacity(face.indices.len() * 2);
+            for v3 in &face.indices {
+                if is_cut(v2) {
+                    let incoming = VertexKey::EdgePoint(v2, v1);
+                    let outgoing = VertexKey::EdgePoint(v2, *v3);
+                    let vertex = self.vertices[v2 as usize];
+                    let previous = self.vertices[v1 as usize];
+                    let next = self.vertices[*v3 as usize];
+                    builder.add_vertex(incoming, vertex + (previous - vertex) * truncate.depth());
+                    builder.add_vertex(outgoing, vertex + (next - vertex) * truncate.depth());
 
-            for index in &face.indices {
-                let v2_key = VertexKey::Seed(*index);
-                if is_identity {
-                    builder.add_flag(FaceKey::Seed(face_index), v1_key, v2_key);
-                    v1_key = v2_key;
-                    continue;
+                    builder.add_flag(FaceKey::Vertex(v2), outgoing, incoming);
+                    ring.push(incoming);
+                    ring.push(outgoing);
+                } else {
+                    ring.push(VertexKey::Seed(v2));
                 }
+                v1 = v2;
+                v2 = *v3;
+            }
 
-                let apex_key = VertexKey::Centroid(face_index);
-                let face_key = FaceKey::PyramidFace(face_index, v1_key);
+            let mut previous = *ring.last().unwrap();
+            for current in ring {
+                builder.add_flag(FaceKey::Seed(i as u32), previous, current);
+                previous = current;
+            }
+        }
+
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
 
-                builder.add_vertex(apex_key, apex_position);
-                builder.add_flag(face_key, v1_key, v2_key);
-                builder.add_flag(face_key, v2_key, apex_key);
-                builder.add_flag(face_key, apex_key, v1_key);
-                v1_key = v2_key;
+    /// Applies the `join` operator and returns the resulting polyhedron.
+    pub fn join(self) -> Polyhedron {
+        self.build_join(&Progress::default())
+    }
+
+    fn build_join(self, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+
+        for i in 0..self.vertices.len() {
+            builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+        }
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let face_vertex_key = VertexKey::Centroid(i as u32);
+            builder.add_vertex(face_vertex_key, center(self.face_vertices(face)));
+
+            let mut v1 = face.last().1;
+            for v2 in &face.indices {
+                let face_key = FaceKey::edge(v1, *v2);
+                builder.add_flag(face_key, VertexKey::Seed(*v2), face_vertex_key);
+                builder.add_flag(face_key, face_vertex_key, VertexKey::Seed(v1));
+                v1 = *v2;
             }
         }
 
-        builder.build_polyhedron()
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
     }
 
-    /// Applies the `dual` operator and returns the resulting polyhedron.
-    pub fn dual(self) -> Polyhedron {
-        let mut builder = Builder::new();
+    /// Applies the `ortho` operator and returns the resulting polyhedron.
+    pub fn ortho(self) -> Polyhedron {
+        self.build_ortho(&Progress::default())
+    }
+
+    fn build_ortho(self, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+
+        for i in 0..self.vertices.len() {
+            builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+        }
 
-        let mut face_map = Vec::with_capacity(self.vertices.len());
-        face_map.resize(face_map.capacity(), FnvHashMap::default());
         for i in 0..self.faces.len() {
-            let center = center(self.face_vertices(&self.faces[i]));
-            builder.add_vertex(VertexKey::Centroid(i as u32), center);
+            let face = &self.faces[i];
+            let face_vertex_key = VertexKey::Centroid(i as u32);
+            builder.add_vertex(face_vertex_key, center(self.face_vertices(face)));
+
+            let (mut v1, mut v2) = face.last();
+            for v3 in &face.indices {
+                if v2 < *v3 {
+                    let midpoint = self.vertices[v2 as usize].midpoint(self.vertices[*v3 as usize]);
+                    builder.add_vertex(VertexKey::midpoint(v2, *v3), midpoint);
+                }
+
+                let face_key = FaceKey::Corner(i as u32, v2);
+                builder.add_flag(face_key, VertexKey::Seed(v2), VertexKey::midpoint(v2, *v3));
+                builder.add_flag(face_key, VertexKey::midpoint(v2, *v3), face_vertex_key);
+                builder.add_flag(face_key, face_vertex_key, VertexKey::midpoint(v1, v2));
+                builder.add_flag(face_key, VertexKey::midpoint(v1, v2), VertexKey::Seed(v2));
+                v1 = v2;
+                v2 = *v3;
+            }
         }
 
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
+
+    /// Applies the `expand` operator and returns the resulting polyhedron.
+    pub fn expand(self) -> Polyhedron {
+        self.build_expand(&Progress::default())
+    }
+
+    fn build_expand(self, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        let edge_faces = self.edge_faces();
+
         for i in 0..self.faces.len() {
             let face = &self.faces[i];
+            let face_index = i as u32;
+            let center = center(self.face_vertices(face));
+
             let mut v1 = face.last().1;
+            for v2 in &face.indices {
+                let neighbor = edge_faces[*v2 as usize][&v1];
+                let corner_key = VertexKey::Corner(face_index, v1);
+                let next_corner_key = VertexKey::Corner(face_index, *v2);
+                let edge_key = FaceKey::edge(v1, *v2);
+                builder.add_vertex(corner_key, self.vertices[v1 as usize].midpoint(center));
 
-            let face_vertex_key = VertexKey::Centroid(i as u32);
+                builder.add_flag(FaceKey::Seed(face_index), corner_key, next_corner_key);
+                builder.add_flag(edge_key, next_corner_key, corner_key);
+                builder.add_flag(edge_key, corner_key, VertexKey::Corner(neighbor, v1));
+                builder.add_flag(FaceKey::Vertex(v1), VertexKey::Corner(neighbor, v1), corner_key);
+                v1 = *v2;
+            }
+        }
+
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
+
+    /// Applies the `gyro` operator with the given handedness and returns the resulting polyhedron.
+    pub fn gyro(self, chirality: Chirality) -> Polyhedron {
+        self.build_gyro(chirality, &Progress::default())
+    }
+
+    fn build_gyro(self, chirality: Chirality, progress: &Progress) -> Polyhedron {
+        match chirality {
+            Chirality::Right => self.right_gyro(progress),
+            Chirality::Left => self.reflect().right_gyro(progress).reflect(),
+        }
+    }
+
+    /// Applies the `snub` operator with the given handedness and returns the resulting polyhedron.
+    pub fn snub(self, chirality: Chirality) -> Polyhedron {
+        self.build_snub(chirality, &Progress::default())
+    }
+
+    fn build_snub(self, chirality: Chirality, progress: &Progress) -> Polyhedron {
+        match chirality {
+            Chirality::Right => self.right_snub(progress),
+            Chirality::Left => self.reflect().right_snub(progress).reflect(),
+        }
+    }
+
+    /// Applies the `bevel` operator and returns the resulting polyhedron.
+    pub fn bevel(self) -> Polyhedron {
+        self.build_bevel(&Progress::default())
+    }
+
+    fn build_bevel(self, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        let edge_faces = self.edge_faces();
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let face_index = i as u32;
+            let center = center(self.face_vertices(face));
+
+            let (mut v1, mut v2) = face.last();
+            for v3 in &face.indices {
+                // Each flag vertex is pulled from the seed vertex toward both the neighboring
+                // vertex and the center, which keeps the new faces across each edge planar
+                let first = self.vertices[v1 as usize];
+                let second = self.vertices[v2 as usize];
+                let near_first = VertexKey::Flag(face_index, v1, v2);
+                let near_second = VertexKey::Flag(face_index, v2, v1);
+                let first_position = first + (second - first) / 4.0 + (center - first) / 4.0;
+                let second_position = second + (first - second) / 4.0 + (center - second) / 4.0;
+                builder.add_vertex(near_first, first_position);
+                builder.add_vertex(near_second, second_position);
+
+                let neighbor = edge_faces[v2 as usize][&v1];
+                let edge_key = FaceKey::edge(v1, v2);
+                let next = VertexKey::Flag(face_index, v2, *v3);
+                let across = VertexKey::Flag(neighbor, v1, v2);
+                builder.add_flag(FaceKey::Seed(face_index), near_first, near_second);
+                builder.add_flag(FaceKey::Seed(face_index), near_second, next);
+                builder.add_flag(edge_key, near_second, near_first);
+                builder.add_flag(edge_key, near_first, across);
+                builder.add_flag(FaceKey::Vertex(v1), across, near_first);
+                builder.add_flag(FaceKey::Vertex(v2), next, near_second);
+                v1 = v2;
+                v2 = *v3;
+            }
+        }
+
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
+
+    /// Applies the `meta` operator and returns the resulting polyhedron.
+    pub fn meta(self) -> Polyhedron {
+        self.build_meta(&Progress::default())
+    }
+
+    fn build_meta(self, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+
+        for i in 0..self.vertices.len() {
+            builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+        }
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let face_index = i as u32;
+            let face_vertex_key = VertexKey::Centroid(face_index);
+            builder.add_vertex(face_vertex_key, center(self.face_vertices(face)));
+
+            let mut v1 = face.last().1;
             for v2 in &face.indices {
-                let map = &mut face_map[v1 as usize];
-                map.insert(*v2, face_vertex_key);
+                let midpoint_key = VertexKey::midpoint(v1, *v2);
+                let midpoint = self.vertices[v1 as usize].midpoint(self.vertices[*v2 as usize]);
+                builder.add_vertex(midpoint_key, midpoint);
+
+                let first_key = FaceKey::Flag(face_index, v1, *v2);
+                builder.add_flag(first_key, VertexKey::Seed(v1), midpoint_key);
+                builder.add_flag(first_key, midpoint_key, face_vertex_key);
+                builder.add_flag(first_key, face_vertex_key, VertexKey::Seed(v1));
+
+                let second_key = FaceKey::Flag(face_index, *v2, v1);
+                builder.add_flag(second_key, midpoint_key, VertexKey::Seed(*v2));
+                builder.add_flag(second_key, VertexKey::Seed(*v2), face_vertex_key);
+                builder.add_flag(second_key, face_vertex_key, midpoint_key);
                 v1 = *v2;
             }
         }
 
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
+
+    /// Applies the `needle` operator and returns the resulting polyhedron.
+    pub fn needle(self, needle: operators::Needle) -> Polyhedron {
+        self.build_needle(needle, &Progress::default())
+    }
+
+    fn build_needle(self, needle: operators::Needle, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        let selected = self.select_vertices(needle.selector());
+        let is_pyramid = |vertex: u32| -> bool { selected[vertex as usize] };
+
+        for i in 0..self.vertices.len() {
+            if is_pyramid(i as u32) {
+                builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+            }
+        }
+        for i in 0..self.faces.len() {
+            let center = center(self.face_vertices(&self.faces[i]));
+            builder.add_vertex(VertexKey::Centroid(i as u32), center);
+        }
+
+        let edge_faces = self.edge_faces();
         for i in 0..self.faces.len() {
             let face = &self.faces[i];
+            let face_vertex_key = VertexKey::Centroid(i as u32);
             let mut v1 = face.last().1;
 
             for v2 in &face.indices {
-                let map = &mut face_map[*v2 as usize];
-                let vertex = map.get(&v1).expect("Should be present");
-                builder.add_flag(FaceKey::Vertex(v1), *vertex, VertexKey::Centroid(i as u32));
+                let neighbor_vertex_key = VertexKey::Centroid(edge_faces[*v2 as usize][&v1]);
+                if is_pyramid(v1) {
+                    let face_key = FaceKey::VertexPyramidFace(v1, neighbor_vertex_key);
+                    builder.add_flag(face_key, neighbor_vertex_key, face_vertex_key);
+                    builder.add_flag(face_key, face_vertex_key, VertexKey::Seed(v1));
+                    builder.add_flag(face_key, VertexKey::Seed(v1), neighbor_vertex_key);
+                } else {
+                    builder.add_flag(FaceKey::Vertex(v1), neighbor_vertex_key, face_vertex_key);
+                }
                 v1 = *v2;
             }
         }
 
-        builder.build_polyhedron()
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
     }
 
-    /// Applies the `ambo` operator and returns the resulting polyhedron.
-    pub fn ambo(self) -> Polyhedron {
-        let mut builder = Builder::new();
+    /// Applies the `zip` operator and returns the resulting polyhedron.
+    pub fn zip(self, zip: operators::Zip) -> Polyhedron {
+        self.build_zip(zip, &Progress::default())
+    }
+
+    fn build_zip(self, zip: operators::Zip, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        let selected = self.select_faces(zip.selector());
+        let is_split = |face_index: u32| -> bool { selected[face_index as usize] };
+        // The vertex standing in for a face along the given edge
+        let edge_vertex_key = |face_index: u32, v1: u32, v2: u32| -> VertexKey {
+            if is_split(face_index) {
+                VertexKey::EdgePoint(v1, v2)
+            } else {
+                VertexKey::Centroid(face_index)
+            }
+        };
+
+        let edge_faces = self.edge_faces();
         for i in 0..self.faces.len() {
             let face = &self.faces[i];
-            let (mut v1, mut v2) = face.last();
+            let face_index = i as u32;
+            let center = center(self.face_vertices(face));
+            let split = is_split(face_index);
+            if !split {
+                builder.add_vertex(VertexKey::Centroid(face_index), center);
+            }
 
+            let (mut v1, mut v2) = face.last();
             for v3 in &face.indices {
-                if v1 < v2 {
-                    let midpoint = self.vertices[v1 as usize].midpoint(self.vertices[v2 as usize]);
-                    builder.add_vertex(VertexKey::midpoint(v1, v2), midpoint);
+                if split {
+                    let first = self.vertices[v1 as usize].to_vec();
+                    let second = self.vertices[v2 as usize].to_vec();
+                    let incoming = VertexKey::EdgePoint(v1, v2);
+                    builder.add_vertex(incoming, (center + first + second) / 3.0);
+
+                    let outgoing = VertexKey::EdgePoint(v2, *v3);
+                    builder.add_flag(FaceKey::Seed(face_index), incoming, outgoing);
+                    builder.add_flag(FaceKey::Vertex(v2), outgoing, incoming);
                 }
 
+                let neighbor = edge_faces[v2 as usize][&v1];
                 builder.add_flag(
-                    FaceKey::Seed(i as u32),
-                    VertexKey::midpoint(v1, v2),
-                    VertexKey::midpoint(v2, *v3),
-                );
-                builder.add_flag(
-                    FaceKey::Vertex(v2),
-                    VertexKey::midpoint(v2, *v3),
-                    VertexKey::midpoint(v1, v2),
+                    FaceKey::Vertex(v1),
+                    edge_vertex_key(neighbor, v2, v1),
+                    edge_vertex_key(face_index, v1, v2),
                 );
                 v1 = v2;
                 v2 = *v3;
             }
         }
-        builder.build_polyhedron()
+
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
+
+    /// Applies the `propeller` operator with the given handedness and returns the resulting
+    /// polyhedron.
+    pub fn propeller(self, chirality: Chirality) -> Polyhedron {
+        self.build_propeller(chirality, &Progress::default())
+    }
+
+    fn build_propeller(self, chirality: Chirality, progress: &Progress) -> Polyhedron {
+        match chirality {
+            Chirality::Right => self.right_propeller(progress),
+            Chirality::Left => self.reflect().right_propeller(progress).reflect(),
+        }
+    }
+
+    /// Applies the `chamfer` operator and returns the resulting polyhedron.
+    pub fn chamfer(self, chamfer: operators::Chamfer) -> Polyhedron {
+        self.build_chamfer(chamfer, &Progress::default())
+    }
+
+    fn build_chamfer(self, chamfer: operators::Chamfer, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        let edge_faces = self.edge_faces();
+        let ratio = chamfer.ratio();
+        let is_selected = |v1: u32, v2: u32| -> bool {
+            let face = &self.faces[edge_faces[v1 as usize][&v2] as usize];
+            let neighbor = &self.faces[edge_faces[v2 as usize][&v1] as usize];
+            let side_counts = (face.indices.len() as u32, neighbor.indices.len() as u32);
+            chamfer.selector().matches(side_counts.0, side_counts.1)
+        };
+
+        let mut centers = Vec::with_capacity(self.faces.len());
+        let mut normals = Vec::with_capacity(self.faces.len());
+        for face in &self.faces {
+            centers.push(center(self.face_vertices(face)));
+            normals.push(normal(self.face_vertices(face)));
+        }
+
+        for i in 0..self.vertices.len() {
+            builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+        }
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let face_index = i as u32;
+
+            // Corners next to a selected edge move into the face, while corners next to an
+            // unselected edge stay in place, so that the edge is still shared with its neighbor
+            let mut ring = Vec::with_capacity(face.indices.len() * 2);
+            let (mut v1, mut v2) = face.last();
+            for v3 in &face.indices {
+                let is_previous_selected = is_selected(v1, v2);
+                let is_next_selected = is_selected(v2, *v3);
+                let seed_key = VertexKey::Seed(v2);
+                let corner_key = VertexKey::Corner(face_index, v2);
+                if !is_previous_selected && !is_next_selected {
+                    ring.push(seed_key);
+                    v1 = v2;
+                    v2 = *v3;
+                    continue;
+                }
+
+                // Raise each corner so that the hexagons on either side of it are as close to
+                // planar as possible; for regular faces they will be exactly planar
+                let vertex = self.vertices[v2 as usize];
+                let to_center = centers[i] - vertex;
+                let lift = |neighbor: u32| -> f64 {
+                    let neighbor_normal = normals[neighbor as usize];
+                    -to_center.dot(neighbor_normal) / (1.0 + normals[i].dot(neighbor_normal))
+                };
+                let mut lifts = Vec::with_capacity(2);
+                if is_previous_selected {
+                    lifts.push(lift(edge_faces[v2 as usize][&v1]));
+                }
+                if is_next_selected {
+                    lifts.push(lift(edge_faces[*v3 as usize][&v2]));
+                }
+                let height = ratio * lifts.iter().sum::<f64>() / lifts.len() as f64;
+                builder.add_vertex(corner_key, vertex + to_center * ratio + normals[i] * height);
+
+                if !is_previous_selected {
+                    ring.push(seed_key);
+                }
+                ring.push(corner_key);
+                if !is_next_selected {
+                    ring.push(seed_key);
+                }
+
+                if is_previous_selected {
+                    let previous_corner_key = VertexKey::Corner(face_index, v1);
+                    let edge_key = FaceKey::edge(v1, v2);
+                    builder.add_flag(edge_key, corner_key, previous_corner_key);
+                    builder.add_flag(edge_key, seed_key, corner_key);
+                    builder.add_flag(edge_key, previous_corner_key, VertexKey::Seed(v1));
+                }
+                v1 = v2;
+                v2 = *v3;
+            }
+
+            let mut previous_key = *ring.last().unwrap();
+            for key in ring {
+                builder.add_flag(FaceKey::Seed(face_index), previous_key, key);
+                previous_key = key;
+            }
+        }
+
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
+
+    /// Applies the `whirl` operator and returns the resulting polyhedron.
+    pub fn whirl(self, whirl: operators::Whirl) -> Polyhedron {
+        self.build_whirl(whirl, &Progress::default())
+    }
+
+    fn build_whirl(self, whirl: operators::Whirl, progress: &Progress) -> Polyhedron {
+        match whirl.chirality() {
+            Chirality::Right => self.right_whirl(whirl.ratio(), progress),
+            Chirality::Left => self.reflect().right_whirl(whirl.ratio(), progress).reflect(),
+        }
+    }
+
+    /// Applies the `loft` operator and returns the resulting polyhedron.
+    pub fn loft(self, loft: operators::Loft) -> Polyhedron {
+        self.build_loft(loft, &Progress::default())
+    }
+
+    fn build_loft(self, loft: operators::Loft, progress: &Progress) -> Polyhedron {
+        self.inset_faces(loft.selector(), 1.0 / 3.0, 0.0, progress)
+    }
+
+    /// Applies the `inset` operator and returns the resulting polyhedron.
+    pub fn inset(self, inset: operators::Inset) -> Polyhedron {
+        self.build_inset(inset, &Progress::default())
+    }
+
+    fn build_inset(self, inset: operators::Inset, progress: &Progress) -> Polyhedron {
+        self.inset_faces(inset.selector(), inset.ratio(), inset.distance(), progress)
+    }
+
+    /// Applies the `extrude` operator and returns the resulting polyhedron.
+    pub fn extrude(self, extrude: operators::Extrude) -> Polyhedron {
+        self.build_extrude(extrude, &Progress::default())
+    }
+
+    fn build_extrude(self, extrude: operators::Extrude, progress: &Progress) -> Polyhedron {
+        self.inset_faces(extrude.selector(), extrude.ratio(), extrude.distance(), progress)
+    }
+
+    /// Applies the `lace` operator and returns the resulting polyhedron.
+    pub fn lace(self, lace: operators::Lace) -> Polyhedron {
+        self.build_lace(lace, &Progress::default())
+    }
+
+    fn build_lace(self, lace: operators::Lace, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        let selected = self.select_faces(lace.selector());
+        let is_affected = |face_index: u32| -> bool { selected[face_index as usize] };
+
+        for i in 0..self.vertices.len() {
+            builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+        }
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let face_index = i as u32;
+            if !is_affected(face_index) {
+                self.add_seed_face(&mut builder, i, |_, _| false);
+                continue;
+            }
+
+            self.add_edge_inner_vertices(&mut builder, i, false);
+            let (mut v1, mut v2) = face.last();
+            for v3 in &face.indices {
+                let inner_key = VertexKey::EdgePoint(v1, v2);
+                let next_inner_key = VertexKey::EdgePoint(v2, *v3);
+                builder.add_flag(FaceKey::Seed(face_index), inner_key, next_inner_key);
+
+                let edge_face_key = FaceKey::Flag(face_index, v1, v2);
+                builder.add_flag(edge_face_key, VertexKey::Seed(v1), VertexKey::Seed(v2));
+                builder.add_flag(edge_face_key, VertexKey::Seed(v2), inner_key);
+                builder.add_flag(edge_face_key, inner_key, VertexKey::Seed(v1));
+
+                let corner_face_key = FaceKey::Corner(face_index, v2);
+                builder.add_flag(corner_face_key, inner_key, VertexKey::Seed(v2));
+                builder.add_flag(corner_face_key, VertexKey::Seed(v2), next_inner_key);
+                builder.add_flag(corner_face_key, next_inner_key, inner_key);
+                v1 = v2;
+                v2 = *v3;
+            }
+        }
+
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
+
+    /// Applies the `stake` operator and returns the resulting polyhedron.
+    pub fn stake(self, stake: operators::Stake) -> Polyhedron {
+        self.build_stake(stake, &Progress::default())
+    }
+
+    fn build_stake(self, stake: operators::Stake, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        let selected = self.select_faces(stake.selector());
+        let is_affected = |face_index: u32| -> bool { selected[face_index as usize] };
+
+        for i in 0..self.vertices.len() {
+            builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+        }
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let face_index = i as u32;
+            if !is_affected(face_index) {
+                self.add_seed_face(&mut builder, i, |_, _| false);
+                continue;
+            }
+
+            let face_vertex_key = VertexKey::Centroid(face_index);
+            builder.add_vertex(face_vertex_key, center(self.face_vertices(face)));
+            self.add_edge_inner_vertices(&mut builder, i, false);
+            let (mut v1, mut v2) = face.last();
+            for v3 in &face.indices {
+                let inner_key = VertexKey::EdgePoint(v1, v2);
+                let next_inner_key = VertexKey::EdgePoint(v2, *v3);
+
+                let edge_face_key = FaceKey::Flag(face_index, v1, v2);
+                builder.add_flag(edge_face_key, VertexKey::Seed(v1), VertexKey::Seed(v2));
+                builder.add_flag(edge_face_key, VertexKey::Seed(v2), inner_key);
+                builder.add_flag(edge_face_key, inner_key, VertexKey::Seed(v1));
+
+                let corner_face_key = FaceKey::Corner(face_index, v2);
+                builder.add_flag(corner_face_key, inner_key, VertexKey::Seed(v2));
+                builder.add_flag(corner_face_key, VertexKey::Seed(v2), next_inner_key);
+                builder.add_flag(corner_face_key, next_inner_key, face_vertex_key);
+                builder.add_flag(corner_face_key, face_vertex_key, inner_key);
+                v1 = v2;
+                v2 = *v3;
+            }
+        }
+
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
+
+    /// Applies the `quinto` operator and returns the resulting polyhedron.
+    pub fn quinto(self, quinto: operators::Quinto) -> Polyhedron {
+        self.build_quinto(quinto, &Progress::default())
+    }
+
+    fn build_quinto(self, quinto: operators::Quinto, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        let selected = self.select_faces(quinto.selector());
+        let is_affected = |face_index: u32| -> bool { selected[face_index as usize] };
+        let edge_faces = self.edge_faces();
+
+        for i in 0..self.vertices.len() {
+            builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+        }
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let face_index = i as u32;
+            if !is_affected(face_index) {
+                self.add_seed_face(&mut builder, i, |v1, v2| {
+                    is_affected(edge_faces[v2 as usize][&v1])
+                });
+                continue;
+            }
+
+            self.add_edge_inner_vertices(&mut builder, i, true);
+            let (mut v1, mut v2) = face.last();
+            for v3 in &face.indices {
+                let inner_key = VertexKey::EdgePoint(v1, v2);
+                let next_inner_key = VertexKey::EdgePoint(v2, *v3);
+                builder.add_flag(FaceKey::Seed(face_index), inner_key, next_inner_key);
+
+                let face_key = FaceKey::Corner(face_index, v2);
+                builder.add_flag(face_key, inner_key, VertexKey::midpoint(v1, v2));
+                builder.add_flag(face_key, VertexKey::midpoint(v1, v2), VertexKey::Seed(v2));
+                builder.add_flag(face_key, VertexKey::Seed(v2), VertexKey::midpoint(v2, *v3));
+                builder.add_flag(face_key, VertexKey::midpoint(v2, *v3), next_inner_key);
+                builder.add_flag(face_key, next_inner_key, inner_key);
+                v1 = v2;
+                v2 = *v3;
+            }
+        }
+
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
+
+    /// Applies the `joined-lace` operator and returns the resulting polyhedron.
+    pub fn joined_lace(self, joined_lace: operators::JoinedLace) -> Polyhedron {
+        self.build_joined_lace(joined_lace, &Progress::default())
+    }
+
+    fn build_joined_lace(
+        self,
+        joined_lace: operators::JoinedLace,
+        progress: &Progress,
+    ) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        let selected = self.select_faces(joined_lace.selector());
+        let is_affected = |face_index: u32| -> bool { selected[face_index as usize] };
+        let edge_faces = self.edge_faces();
+
+        for i in 0..self.vertices.len() {
+            builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+        }
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let face_index = i as u32;
+            if !is_affected(face_index) {
+                self.add_seed_face(&mut builder, i, |_, _| false);
+                continue;
+            }
+
+            self.add_edge_inner_vertices(&mut builder, i, false);
+            let (mut v1, mut v2) = face.last();
+            for v3 in &face.indices {
+                let inner_key = VertexKey::EdgePoint(v1, v2);
+                let next_inner_key = VertexKey::EdgePoint(v2, *v3);
+                builder.add_flag(FaceKey::Seed(face_index), inner_key, next_inner_key);
+
+                // The faces on either side of each edge are joined, unless the other side was
+                // left untouched
+                let edge_face_key = FaceKey::edge(v1, v2);
+                builder.add_flag(edge_face_key, VertexKey::Seed(v2), inner_key);
+                builder.add_flag(edge_face_key, inner_key, VertexKey::Seed(v1));
+                let neighbor = edge_faces[v2 as usize][&v1];
+                if !is_affected(neighbor) {
+                    builder.add_flag(edge_face_key, VertexKey::Seed(v1), VertexKey::Seed(v2));
+                }
+
+                let corner_face_key = FaceKey::Corner(face_index, v2);
+                builder.add_flag(corner_face_key, inner_key, VertexKey::Seed(v2));
+                builder.add_flag(corner_face_key, VertexKey::Seed(v2), next_inner_key);
+                builder.add_flag(corner_face_key, next_inner_key, inner_key);
+                v1 = v2;
+                v2 = *v3;
+            }
+        }
+
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
+
+    /// Applies the `cross` operator and returns the resulting polyhedron.
+    pub fn cross(self, cross: operators::Cross) -> Polyhedron {
+        self.build_cross(cross, &Progress::default())
+    }
+
+    fn build_cross(self, cross: operators::Cross, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        let selected = self.select_faces(cross.selector());
+        let is_affected = |face_index: u32| -> bool { selected[face_index as usize] };
+        let edge_faces = self.edge_faces();
+
+        for i in 0..self.vertices.len() {
+            builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+        }
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let face_index = i as u32;
+            if !is_affected(face_index) {
+                self.add_seed_face(&mut builder, i, |v1, v2| {
+                    is_affected(edge_faces[v2 as usize][&v1])
+                });
+                continue;
+            }
+
+            let face_vertex_key = VertexKey::Centroid(face_index);
+            builder.add_vertex(face_vertex_key, center(self.face_vertices(face)));
+            self.add_edge_inner_vertices(&mut builder, i, true);
+            let (mut v1, mut v2) = face.last();
+            for v3 in &face.indices {
+                let inner_key = VertexKey::EdgePoint(v1, v2);
+                let next_inner_key = VertexKey::EdgePoint(v2, *v3);
+                let midpoint_key = VertexKey::midpoint(v1, v2);
+
+                let first_key = FaceKey::Flag(face_index, v1, v2);
+                builder.add_flag(first_key, VertexKey::Seed(v1), midpoint_key);
+                builder.add_flag(first_key, midpoint_key, inner_key);
+                builder.add_flag(first_key, inner_key, VertexKey::Seed(v1));
+
+                let second_key = FaceKey::Flag(face_index, v2, v1);
+                builder.add_flag(second_key, midpoint_key, VertexKey::Seed(v2));
+                builder.add_flag(second_key, VertexKey::Seed(v2), inner_key);
+                builder.add_flag(second_key, inner_key, midpoint_key);
+
+                let corner_face_key = FaceKey::Corner(face_index, v2);
+                builder.add_flag(corner_face_key, inner_key, VertexKey::Seed(v2));
+                builder.add_flag(corner_face_key, VertexKey::Seed(v2), next_inner_key);
+                builder.add_flag(corner_face_key, next_inner_key, face_vertex_key);
+                builder.add_flag(corner_face_key, face_vertex_key, inner_key);
+                v1 = v2;
+                v2 = *v3;
+            }
+        }
+
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
+
+    fn right_gyro(self, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+
+        for i in 0..self.vertices.len() {
+            builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+        }
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let face_vertex_key = VertexKey::Centroid(i as u32);
+            builder.add_vertex(face_vertex_key, center(self.face_vertices(face)));
+
+            let (mut v1, mut v2) = face.last();
+            for v3 in &face.indices {
+                let first = self.vertices[v1 as usize];
+                let second = self.vertices[v2 as usize];
+                let near_first = VertexKey::EdgePoint(v1, v2);
+                let near_second = VertexKey::EdgePoint(v2, v1);
+                builder.add_vertex(near_first, first + (second - first) / 3.0);
+                builder.add_vertex(near_second, second + (first - second) / 3.0);
+
+                let face_key = FaceKey::Corner(i as u32, v2);
+                builder.add_flag(face_key, face_vertex_key, near_first);
+                builder.add_flag(face_key, near_first, near_second);
+                builder.add_flag(face_key, near_second, VertexKey::Seed(v2));
+                builder.add_flag(face_key, VertexKey::Seed(v2), VertexKey::EdgePoint(v2, *v3));
+                builder.add_flag(face_key, VertexKey::EdgePoint(v2, *v3), face_vertex_key);
+                v1 = v2;
+                v2 = *v3;
+            }
+        }
+
+        let chirality = self.chirality.or(Some(Chirality::Right));
+        builder.build_polyhedron(chirality, &self.seed_faces)
+    }
+
+    fn right_propeller(self, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+
+        for i in 0..self.vertices.len() {
+            builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+        }
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let (mut v1, mut v2) = face.last();
+            for v3 in &face.indices {
+                let first = self.vertices[v1 as usize];
+                let second = self.vertices[v2 as usize];
+                let near_first = VertexKey::EdgePoint(v1, v2);
+                let near_second = VertexKey::EdgePoint(v2, v1);
+                let next = VertexKey::EdgePoint(v2, *v3);
+                builder.add_vertex(near_first, first + (second - first) / 3.0);
+                builder.add_vertex(near_second, second + (first - second) / 3.0);
+
+                builder.add_flag(FaceKey::Seed(i as u32), near_first, next);
+
+                let face_key = FaceKey::Corner(i as u32, v2);
+                builder.add_flag(face_key, near_first, near_second);
+                builder.add_flag(face_key, near_second, VertexKey::Seed(v2));
+                builder.add_flag(face_key, VertexKey::Seed(v2), next);
+                builder.add_flag(face_key, next, near_first);
+                v1 = v2;
+                v2 = *v3;
+            }
+        }
+
+        let chirality = self.chirality.or(Some(Chirality::Right));
+        builder.build_polyhedron(chirality, &self.seed_faces)
+    }
+
+    fn right_whirl(self, ratio: f64, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+
+        for i in 0..self.vertices.len() {
+            builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+        }
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let face_index = i as u32;
+            let center = center(self.face_vertices(face));
+
+            let (mut v1, mut v2) = face.last();
+            for v3 in &face.indices {
+                let first = self.vertices[v1 as usize];
+                let second = self.vertices[v2 as usize];
+                let near_first = VertexKey::EdgePoint(v1, v2);
+                let near_second = VertexKey::EdgePoint(v2, v1);
+                let near_first_position = first + (second - first) / 3.0;
+                builder.add_vertex(near_first, near_first_position);
+                builder.add_vertex(near_second, second + (first - second) / 3.0);
+
+                let inner_key = VertexKey::Corner(face_index, v1);
+                let next_inner_key = VertexKey::Corner(face_index, v2);
+                builder.add_vertex(inner_key, center + (near_first_position - center) * ratio);
+
+                builder.add_flag(FaceKey::Seed(face_index), inner_key, next_inner_key);
+
+                let face_key = FaceKey::Corner(face_index, v1);
+                builder.add_flag(face_key, inner_key, near_first);
+                builder.add_flag(face_key, near_first, near_second);
+                builder.add_flag(face_key, near_second, VertexKey::Seed(v2));
+                builder.add_flag(face_key, VertexKey::Seed(v2), VertexKey::EdgePoint(v2, *v3));
+                builder.add_flag(face_key, VertexKey::EdgePoint(v2, *v3), next_inner_key);
+                builder.add_flag(face_key, next_inner_key, inner_key);
+                v1 = v2;
+                v2 = *v3;
+            }
+        }
+
+        let chirality = self.chirality.or(Some(Chirality::Right));
+        builder.build_polyhedron(chirality, &self.seed_faces)
+    }
+
+    fn right_snub(self, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        let edge_faces = self.edge_faces();
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let face_index = i as u32;
+            let face_center = center(self.face_vertices(face));
+
+            let (mut v1, mut v2) = face.last();
+            for v3 in &face.indices {
+                // Each vertex sits at the center of the matching pentagon produced by `gyro`
+                let previous = self.vertices[v1 as usize];
+                let vertex = self.vertices[v2 as usize];
+                let next = self.vertices[*v3 as usize];
+                let pentagon = [
+                    face_center,
+                    previous + (vertex - previous) / 3.0,
+                    vertex + (previous - vertex) / 3.0,
+                    vertex,
+                    vertex + (next - vertex) / 3.0,
+                ];
+                let corner_key = VertexKey::Corner(face_index, v2);
+                builder.add_vertex(corner_key, center(pentagon.iter().cloned()));
+
+                let previous_neighbor = edge_faces[v2 as usize][&v1];
+                let next_neighbor = edge_faces[*v3 as usize][&v2];
+                let flags = [
+                    (FaceKey::Seed(face_index), VertexKey::Corner(face_index, v1)),
+                    (FaceKey::EdgePoint(v1, v2), VertexKey::Corner(previous_neighbor, v1)),
+                    (FaceKey::EdgePoint(v2, v1), VertexKey::Corner(previous_neighbor, v2)),
+                    (FaceKey::Vertex(v2), VertexKey::Corner(next_neighbor, v2)),
+                    (FaceKey::EdgePoint(v2, *v3), VertexKey::Corner(face_index, *v3)),
+                ];
+                for (face_key, source) in flags.iter() {
+                    builder.add_flag(*face_key, *source, corner_key);
+                }
+                v1 = v2;
+                v2 = *v3;
+            }
+        }
+
+        let chirality = self.chirality.or(Some(Chirality::Right));
+        builder.build_polyhedron(chirality, &self.seed_faces)
+    }
+
+    /// Applies the `reflect` operator and returns the resulting polyhedron. The polyhedron is
+    /// mirrored across the YZ plane, and the winding of each face is reversed so that they continue
+    /// to face outward.
+    pub fn reflect(mut self) -> Polyhedron {
+        for vertex in self.vertices.iter_mut() {
+            vertex.x = -vertex.x;
+        }
+        for face in self.faces.iter_mut() {
+            face.indices.reverse();
+        }
+        self.chirality = self.chirality.map(Chirality::reversed);
+        self
+    }
+
+    /// Replaces each face matching the selector with a copy that has been shrunk towards its center by `ratio` and moved along its normal
+    /// by `distance`, relative to its mean radius. The copy is joined to the original edges by a
+    /// ring of quadrilaterals.
+    fn inset_faces(
+        self,
+        selector: &FaceSelector,
+        ratio: f64,
+        distance: f64,
+        progress: &Progress,
+    ) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+        let selected = self.select_faces(selector);
+
+        for i in 0..self.vertices.len() {
+            builder.add_vertex(VertexKey::Seed(i as u32), self.vertices[i]);
+        }
+
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let face_index = i as u32;
+            if !selected[face_index as usize] {
+                self.add_seed_face(&mut builder, i, |_, _| false);
+                continue;
+            }
+
+            let vertices = self.face_vertices(face);
+            let center = center(vertices.clone());
+            let offset = normal(vertices.clone()) * (distance * mean_distance(vertices, center));
+            let mut v1 = face.last().1;
+            for v2 in &face.indices {
+                let vertex = self.vertices[v1 as usize];
+                let inner_key = VertexKey::Corner(face_index, v1);
+                let next_inner_key = VertexKey::Corner(face_index, *v2);
+                builder.add_vertex(inner_key, vertex + (center - vertex) * ratio + offset);
+
+                builder.add_flag(FaceKey::Seed(face_index), inner_key, next_inner_key);
+
+                let face_key = FaceKey::Corner(face_index, v1);
+                builder.add_flag(face_key, VertexKey::Seed(v1), VertexKey::Seed(*v2));
+                builder.add_flag(face_key, VertexKey::Seed(*v2), next_inner_key);
+                builder.add_flag(face_key, next_inner_key, inner_key);
+                builder.add_flag(face_key, inner_key, VertexKey::Seed(v1));
+                v1 = *v2;
+            }
+        }
+
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
+
+    /// Adds the seed face with the given index unchanged, except that each edge for which
+    /// `is_split` returns true passes through its midpoint.
+    fn add_seed_face(
+        &self,
+        builder: &mut Builder,
+        face_index: usize,
+        is_split: impl Fn(u32, u32) -> bool,
+    ) {
+        let face = &self.faces[face_index];
+        let face_key = FaceKey::Seed(face_index as u32);
+        let mut v1 = face.last().1;
+        for v2 in &face.indices {
+            if is_split(v1, *v2) {
+                let midpoint_key = VertexKey::midpoint(v1, *v2);
+                builder.add_flag(face_key, VertexKey::Seed(v1), midpoint_key);
+                builder.add_flag(face_key, midpoint_key, VertexKey::Seed(*v2));
+            } else {
+                builder.add_flag(face_key, VertexKey::Seed(v1), VertexKey::Seed(*v2));
+            }
+            v1 = *v2;
+        }
+    }
+
+    /// Adds a vertex for each edge of the seed face with the given index, halfway between the
+    /// midpoint of the edge and the center of the face. When `with_midpoints` is true, the
+    /// midpoints themselves are added as well.
+    fn add_edge_inner_vertices(
+        &self,
+        builder: &mut Builder,
+        face_index: usize,
+        with_midpoints: bool,
+    ) {
+        let face = &self.faces[face_index];
+        let center = center(self.face_vertices(face));
+        let mut v1 = face.last().1;
+        for v2 in &face.indices {
+            let midpoint = self.vertices[v1 as usize].midpoint(self.vertices[*v2 as usize]);
+            if with_midpoints {
+                builder.add_vertex(VertexKey::midpoint(v1, *v2), midpoint);
+            }
+            builder.add_vertex(VertexKey::EdgePoint(v1, *v2), midpoint.midpoint(center));
+            v1 = *v2;
+        }
+    }
+
+    /// Returns, for each vertex, a map from each neighboring vertex to the index of the face
+    /// containing the directed edge between them.
+    pub fn edge_faces(&self) -> Vec<FnvHashMap<u32, u32>> {
+        let mut edge_faces = Vec::with_capacity(self.vertices.len());
+        edge_faces.resize(edge_faces.capacity(), FnvHashMap::default());
+        for i in 0..self.faces.len() {
+            let face = &self.faces[i];
+            let mut v1 = face.last().1;
+            for v2 in &face.indices {
+                edge_faces[v1 as usize].insert(*v2, i as u32);
+                v1 = *v2;
+            }
+        }
+        edge_faces
+    }
+
+    /// Returns the number of faces meeting at each vertex.
+    fn vertex_degrees(&self) -> Vec<u32> {
+        let mut degrees = vec![0; self.vertices.len()];
+        for face in &self.faces {
+            for index in &face.indices {
+                degrees[*index as usize] += 1;
+            }
+        }
+        degrees
     }
 }
 
-fn normal(mut vertices: impl Iterator<Item = Vertex>) -> Vector3<f64> {
+/// Returns the unit normal of a polygon whose vertices run counterclockwise when viewed from the
+/// side it faces. The normal of a polygon with no area is not a number.
+pub fn normal(mut vertices: impl Iterator<Item = Vertex>) -> Vector3<f64> {
     // Using a vertex near the polygon reduces error for polygons far from the origin
     let origin = Point3::origin();
     let first = vertices.next().unwrap_or(origin);
@@ -255,7 +1388,29 @@ fn normal(mut vertices: impl Iterator<Item = Vertex>) -> Vector3<f64> {
     normal.normalize()
 }
 
-fn center(vertices: impl Iterator<Item = Vertex>) -> Point3<f64> {
+fn area(mut vertices: impl Iterator<Item = Vertex>) -> f64 {
+    let first = match vertices.next() {
+        Some(first) => first,
+        None => return 0.0,
+    };
+
+    let mut sum = Vector3 {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    let mut previous = Vector3::zero();
+    for vertex in vertices {
+        let current = vertex - first;
+        sum += previous.cross(current);
+        previous = current;
+    }
+
+    sum.magnitude() / 2.0
+}
+
+/// Returns the mean of the vertices, which is not a number if there are none.
+pub fn center(vertices: impl Iterator<Item = Vertex>) -> Point3<f64> {
     let mut center = Point3::<f64>::origin();
     let mut count = 0u32;
     for vertex in vertices {
@@ -265,7 +1420,9 @@ fn center(vertices: impl Iterator<Item = Vertex>) -> Point3<f64> {
     center / count as f64
 }
 
-fn mean_distance(vertices: impl Iterator<Item = Vertex>, point: Point3<f64>) -> f64 {
+/// Returns the mean distance from the point to each vertex, which is not a number if there are
+/// none.
+pub fn mean_distance(vertices: impl Iterator<Item = Vertex>, point: Point3<f64>) -> f64 {
     use cgmath::MetricSpace;
 
     let mut distance = 0.0;
@@ -1,27 +1,57 @@
-use super::{Face, FaceKey, Polyhedron, Vertex, VertexKey};
+use super::progress::Progress;
+use super::{Chirality, Face, FaceKey, Polyhedron, Vertex, VertexKey};
 use cgmath::Point3;
 use fnv::FnvHashMap;
 use std::hash::Hash;
 
+/// Collects the vertices and faces of a polyhedron as an operator builds it. Each vertex and face
+/// is named by a key, and each face is given as flags: the directed edges running around it, in
+/// the same direction as around the faces of the original polyhedron. Flags may be added in any
+/// order.
 pub struct Builder {
     flags: FnvHashMap<FaceKey, BuilderFace>,
     indices: FnvHashMap<VertexKey, u32>,
     vertices: Vec<Vertex>,
+    progress: Progress,
+    is_cancelled: bool,
 }
 
 impl Builder {
     pub fn new() -> Builder {
+        Builder::with_progress(&Progress::default())
+    }
+
+    /// Creates a builder reporting to the observer of a build, which ignores whatever it is given
+    /// once the build is cancelled.
+    pub(super) fn with_progress(progress: &Progress) -> Builder {
         Builder {
             flags: FnvHashMap::default(),
             indices: FnvHashMap::default(),
             vertices: Vec::new(),
+            progress: progress.clone(),
+            is_cancelled: false,
         }
     }
 
-    pub fn build_polyhedron(self) -> Polyhedron {
-        let mut faces = Vec::with_capacity(self.flags.len());
+    /// Returns whether the build has been cancelled, remembering once it has.
+    fn is_cancelled(&mut self) -> bool {
+        self.is_cancelled = self.is_cancelled || self.progress.is_cancelled();
+        self.is_cancelled
+    }
 
-        for flag in self.flags {
+    /// Builds the polyhedron, given its handedness and the seed faces of the polyhedron the keys
+    /// refer to, as returned by [`Polyhedron::seed_faces`]. A cancelled build stops early, leaving
+    /// out the faces not yet assembled.
+    pub fn build_polyhedron(
+        mut self,
+        chirality: Option<Chirality>,
+        seed_faces: &[Option<u32>],
+    ) -> Polyhedron {
+        let total = if self.is_cancelled() { 0 } else { self.flags.len() };
+        let mut faces = Vec::with_capacity(total);
+        let mut descendants = Vec::with_capacity(total);
+
+        for flag in self.flags.into_iter().take(total) {
             let face = flag.1;
             // Start at an arbitrary vertex
             let start = match face.first() {
@@ -45,15 +75,28 @@ impl Builder {
             }
 
             faces.push(Face { indices });
+            descendants.push(flag.0.parent_face().and_then(|face| seed_faces[face as usize]));
+
+            self.progress.face_built(faces.len(), total);
+            if self.progress.is_cancelled() {
+                break;
+            }
         }
 
         Polyhedron {
             vertices: self.vertices,
             faces,
+            chirality,
+            seed_faces: descendants,
         }
     }
 
+    /// Adds a vertex at the given position. Adding a key that was already added does nothing, so
+    /// neighboring faces may each add the vertices they share.
     pub fn add_vertex(&mut self, key: VertexKey, position: Point3<f64>) {
+        if self.is_cancelled() {
+            return;
+        }
         if self.indices.contains_key(&key) {
             // TODO: Either panic or return a Result with an Error
             return;
@@ -70,8 +113,16 @@ impl Builder {
         assert_eq!(self.vertices.len(), self.indices.len());
     }
 
+    /// Adds the edge from `source` to `destination` to the face, creating the face if needed.
+    /// Both vertices must be added before the polyhedron is built.
+    /// # Restrictions
+    /// Panics if `source` and `destination` are the same, or if the face already has a different
+    /// edge leaving `source`.
     pub fn add_flag(&mut self, face: FaceKey, source: VertexKey, destination: VertexKey) {
         assert_ne!(source, destination);
+        if self.is_cancelled() {
+            return;
+        }
         match self.flags.get_mut(&face) {
             Some(vertex_list) => {
                 vertex_list.add_edge(source, destination);
@@ -85,6 +136,12 @@ impl Builder {
     }
 }
 
+impl Default for Builder {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
 #[derive(Clone)]
 struct BuilderFace {
     edges: Vec<Edge>,
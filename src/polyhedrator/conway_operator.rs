@@ -0,0 +1,194 @@
+//! The trait operators implement to build a polyhedron from another, which lets operators be
+//! defined outside the library and written in notation with a symbol of their own.
+
+use super::operators::{Ambo, Dual, Kis};
+use super::{
+    center, mean_distance, normal, Builder, Chirality, Counts, FaceKey, Polyhedron, VertexKey,
+};
+use cgmath::EuclideanSpace;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// An operator which builds a new polyhedron from the vertices and faces of another. Implement it
+/// to define an operator, and register it with a [`NotationContext`] to write it in notation.
+///
+/// [`NotationContext`]: super::NotationContext
+pub trait ConwayOperator: std::fmt::Debug + Send + Sync {
+    /// Adds the vertices and faces of the result to the builder. Keys name each vertex and face
+    /// by what they are built from in the polyhedron, so that neighboring faces share vertices.
+    fn apply(&self, polyhedron: &Polyhedron, builder: &mut Builder);
+
+    /// Predicts the counts of the result from those of the polyhedron, without building it. This
+    /// is checked against the size limit before anything is built.
+    fn transform_counts(&self, counts: &Counts) -> Counts;
+
+    /// Returns the handedness of the result, if the operator is chiral. A polyhedron that already
+    /// has a handedness keeps it.
+    fn chirality(&self) -> Option<Chirality> {
+        None
+    }
+}
+
+/// An operator defined outside the library, along with the symbol it is written with.
+#[derive(Clone, Debug)]
+pub struct CustomOperator {
+    symbol: char,
+    operator: Arc<dyn ConwayOperator>,
+}
+
+impl CustomOperator {
+    pub fn new(symbol: char, operator: impl ConwayOperator + 'static) -> Self {
+        CustomOperator {
+            symbol,
+            operator: Arc::new(operator),
+        }
+    }
+
+    pub fn symbol(&self) -> char {
+        self.symbol
+    }
+
+    pub fn operator(&self) -> &dyn ConwayOperator {
+        self.operator.as_ref()
+    }
+}
+
+/// Custom operators are equal if they share a symbol and were registered as the same operator.
+impl PartialEq for CustomOperator {
+    fn eq(&self, other: &Self) -> bool {
+        let same_operator = std::ptr::eq(
+            Arc::as_ptr(&self.operator) as *const u8,
+            Arc::as_ptr(&other.operator) as *const u8,
+        );
+        self.symbol == other.symbol && same_operator
+    }
+}
+
+impl Eq for CustomOperator {}
+
+impl Hash for CustomOperator {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.symbol.hash(state);
+        (Arc::as_ptr(&self.operator) as *const u8).hash(state);
+    }
+}
+
+impl PartialOrd for CustomOperator {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match self.symbol.cmp(&other.symbol) {
+            std::cmp::Ordering::Equal if self != other => None,
+            ordering => Some(ordering),
+        }
+    }
+}
+
+impl ConwayOperator for Ambo {
+    fn apply(&self, polyhedron: &Polyhedron, builder: &mut Builder) {
+        for i in 0..polyhedron.faces.len() {
+            let face = &polyhedron.faces[i];
+            let (mut v1, mut v2) = face.last();
+
+            for v3 in &face.indices {
+                if v1 < v2 {
+                    let first = polyhedron.vertices[v1 as usize];
+                    let midpoint = first.midpoint(polyhedron.vertices[v2 as usize]);
+                    builder.add_vertex(VertexKey::midpoint(v1, v2), midpoint);
+                }
+
+                builder.add_flag(
+                    FaceKey::Seed(i as u32),
+                    VertexKey::midpoint(v1, v2),
+                    VertexKey::midpoint(v2, *v3),
+                );
+                builder.add_flag(
+                    FaceKey::Vertex(v2),
+                    VertexKey::midpoint(v2, *v3),
+                    VertexKey::midpoint(v1, v2),
+                );
+                v1 = v2;
+                v2 = *v3;
+            }
+        }
+    }
+
+    fn transform_counts(&self, counts: &Counts) -> Counts {
+        counts.ambo()
+    }
+}
+
+impl ConwayOperator for Dual {
+    fn apply(&self, polyhedron: &Polyhedron, builder: &mut Builder) {
+        for i in 0..polyhedron.faces.len() {
+            let center = center(polyhedron.face_vertices(&polyhedron.faces[i]));
+            builder.add_vertex(VertexKey::Centroid(i as u32), center);
+        }
+
+        let edge_faces = polyhedron.edge_faces();
+        for i in 0..polyhedron.faces.len() {
+            let face = &polyhedron.faces[i];
+            let mut v1 = face.last().1;
+
+            for v2 in &face.indices {
+                let neighbor = edge_faces[*v2 as usize][&v1];
+                builder.add_flag(
+                    FaceKey::Vertex(v1),
+                    VertexKey::Centroid(neighbor),
+                    VertexKey::Centroid(i as u32),
+                );
+                v1 = *v2;
+            }
+        }
+    }
+
+    fn transform_counts(&self, counts: &Counts) -> Counts {
+        counts.dual()
+    }
+}
+
+impl ConwayOperator for Kis {
+    fn apply(&self, polyhedron: &Polyhedron, builder: &mut Builder) {
+        let selected = polyhedron.select_faces(self.selector());
+
+        for i in 0..polyhedron.vertices.len() {
+            let vertex = polyhedron.vertices[i];
+            builder.add_vertex(VertexKey::Seed(i as u32), vertex);
+        }
+
+        for face_index in 0..polyhedron.faces.len() {
+            let face = &polyhedron.faces[face_index];
+            let face_index = face_index as u32;
+            let is_identity = !selected[face_index as usize];
+            let mut v1_key = VertexKey::Seed(*face.indices.last().unwrap());
+
+            let apex_position = {
+                let vertices = polyhedron.face_vertices(face);
+                let center = center(vertices.clone());
+                let normal = normal(vertices.clone());
+                let dist_to_center = mean_distance(vertices.clone(), center);
+                center + normal * (self.apex_scale() * dist_to_center)
+            };
+
+            for index in &face.indices {
+                let v2_key = VertexKey::Seed(*index);
+                if is_identity {
+                    builder.add_flag(FaceKey::Seed(face_index), v1_key, v2_key);
+                    v1_key = v2_key;
+                    continue;
+                }
+
+                let apex_key = VertexKey::Centroid(face_index);
+                let face_key = FaceKey::PyramidFace(face_index, v1_key);
+
+                builder.add_vertex(apex_key, apex_position);
+                builder.add_flag(face_key, v1_key, v2_key);
+                builder.add_flag(face_key, v2_key, apex_key);
+                builder.add_flag(face_key, apex_key, v1_key);
+                v1_key = v2_key;
+            }
+        }
+    }
+
+    fn transform_counts(&self, counts: &Counts) -> Counts {
+        counts.kis(self.selector())
+    }
+}
@@ -0,0 +1,780 @@
+//! Predicts how many vertices, edges and faces operators produce, without building the
+//! polyhedron. Each operator maps the counts of a polyhedron, along with the histograms of its
+//! vertex degrees and face sides, to those of the result.
+//!
+//! Operators that select only some faces or vertices need the histograms to count them. Where a
+//! histogram is unknown, or a selection depends on more than the number of sides, every face or
+//! vertex is assumed to be selected instead, and the prediction becomes an upper bound.
+//!
+//! Counts too large to represent, such as those of many repeated operators, overflow rather than
+//! wrapping around, and are always refused by a size limit.
+
+use super::operators::{
+    Ambo, Dual, EdgeSelector, FaceSelector, GoldbergCoxeter, Operator, VertexSelector,
+};
+use super::{ConwayOperator, Polyhedron};
+use std::collections::BTreeMap;
+
+/// The number of vertices with each degree, or of faces with each number of sides.
+pub type Histogram = BTreeMap<u32, usize>;
+
+/// The number of vertices, edges and faces of a polyhedron, predicted or counted.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Counts {
+    vertices: usize,
+    edges: usize,
+    faces: usize,
+    vertex_degrees: Option<Histogram>,
+    face_sides: Option<Histogram>,
+    is_exact: bool,
+    is_overflowed: bool,
+}
+
+/// A count too large to represent, found while predicting counts.
+struct Overflow;
+
+type Checked<T> = Result<T, Overflow>;
+
+impl Counts {
+    /// Counts the elements of a polyhedron.
+    pub fn of(polyhedron: &Polyhedron) -> Self {
+        let mut vertex_degrees = vec![0; polyhedron.vertices.len()];
+        let mut face_sides = Histogram::new();
+        for face in &polyhedron.faces {
+            *face_sides.entry(face.indices.len() as u32).or_insert(0) += 1;
+            for &vertex in &face.indices {
+                vertex_degrees[vertex as usize] += 1;
+            }
+        }
+        let mut degrees = Histogram::new();
+        for degree in vertex_degrees {
+            *degrees.entry(degree).or_insert(0) += 1;
+        }
+
+        let sides: usize = face_sides.iter().map(|(&sides, &count)| sides as usize * count).sum();
+        Counts {
+            vertices: polyhedron.vertices.len(),
+            edges: sides / 2,
+            faces: polyhedron.faces.len(),
+            vertex_degrees: Some(degrees),
+            face_sides: Some(face_sides),
+            is_exact: true,
+            is_overflowed: false,
+        }
+    }
+
+    /// Creates exact counts whose histograms are unknown, such as for an operator defined outside
+    /// the library.
+    pub fn new(vertices: usize, edges: usize, faces: usize) -> Self {
+        Counts {
+            vertices,
+            edges,
+            faces,
+            vertex_degrees: None,
+            face_sides: None,
+            is_exact: true,
+            is_overflowed: false,
+        }
+    }
+
+    /// Creates counts too large to represent. Every count is `usize::MAX`, and the histograms are
+    /// unknown.
+    pub fn overflowed() -> Self {
+        Counts {
+            vertices: usize::MAX,
+            edges: usize::MAX,
+            faces: usize::MAX,
+            vertex_degrees: None,
+            face_sides: None,
+            is_exact: false,
+            is_overflowed: true,
+        }
+    }
+
+    /// Sets the number of vertices with each degree, and of faces with each number of sides.
+    pub fn with_histograms(self, vertex_degrees: Histogram, face_sides: Histogram) -> Self {
+        Counts {
+            vertex_degrees: Some(vertex_degrees),
+            face_sides: Some(face_sides),
+            ..self
+        }
+    }
+
+    /// Marks the counts as an upper bound, rather than exact.
+    pub fn into_upper_bound(self) -> Self {
+        Counts {
+            is_exact: false,
+            ..self
+        }
+    }
+
+    pub fn vertices(&self) -> usize {
+        self.vertices
+    }
+
+    pub fn edges(&self) -> usize {
+        self.edges
+    }
+
+    pub fn faces(&self) -> usize {
+        self.faces
+    }
+
+    /// Returns the number of vertices where each number of faces meet, if it is known.
+    pub fn vertex_degrees(&self) -> Option<&Histogram> {
+        self.vertex_degrees.as_ref()
+    }
+
+    /// Returns the number of faces with each number of sides, if it is known.
+    pub fn face_sides(&self) -> Option<&Histogram> {
+        self.face_sides.as_ref()
+    }
+
+    /// Returns whether the counts are exact, rather than an upper bound.
+    pub fn is_exact(&self) -> bool {
+        self.is_exact
+    }
+
+    /// Returns whether some count was too large to represent, in which case the counts are only
+    /// known to be enormous.
+    pub fn is_overflowed(&self) -> bool {
+        self.is_overflowed
+    }
+
+    /// Predicts the counts after applying each operator in order, like
+    /// [`Polyhedron::apply_iter`].
+    pub fn apply_iter<'a>(&self, operators: impl IntoIterator<Item = &'a Operator>) -> Counts {
+        let mut counts = self.clone();
+        for operator in operators {
+            counts = operator.transform_counts(&counts);
+        }
+        counts
+    }
+
+    /// Applies a transform, returning overflowed counts if these counts or the result overflow.
+    fn transform(&self, transform: impl FnOnce(&Counts) -> Checked<Counts>) -> Counts {
+        if self.is_overflowed {
+            return self.clone();
+        }
+        transform(self).unwrap_or_else(|Overflow| Counts::overflowed())
+    }
+
+    /// Predicts the counts of `dual`, which swaps the vertices and faces.
+    pub fn dual(&self) -> Counts {
+        Counts {
+            vertices: self.faces,
+            faces: self.vertices,
+            vertex_degrees: self.face_sides.clone(),
+            face_sides: self.vertex_degrees.clone(),
+            ..self.clone()
+        }
+    }
+
+    /// Predicts the counts of `ambo`, which replaces each edge with a vertex.
+    pub fn ambo(&self) -> Counts {
+        self.transform(Counts::checked_ambo)
+    }
+
+    /// Predicts the counts of `kis` acting on the faces matching the selector.
+    pub fn kis(&self, selector: &FaceSelector) -> Counts {
+        self.transform(|counts| counts.checked_kis(counts.select_faces(selector)?))
+    }
+
+    fn checked_ambo(&self) -> Checked<Counts> {
+        Ok(Counts {
+            vertices: self.edges,
+            edges: product(2, self.edges)?,
+            faces: sum(&[self.faces, self.vertices])?,
+            vertex_degrees: Some(single(4, self.edges)),
+            face_sides: union(&self.face_sides, &self.vertex_degrees)?,
+            is_exact: self.is_exact,
+            is_overflowed: false,
+        })
+    }
+
+    fn checked_kis(&self, selection: Selection) -> Checked<Counts> {
+        let vertex_degrees = if selection.count == 0 {
+            self.vertex_degrees.clone()
+        } else if selection.is_all {
+            let doubled = self.vertex_degrees.as_ref().map(|d| scaled(d, 2)).transpose()?;
+            union(&doubled, &selection.histogram)?
+        } else {
+            None
+        };
+        let face_sides = selection
+            .remainder(&self.face_sides)
+            .map(|remainder| plus(remainder, 3, selection.total))
+            .transpose()?;
+
+        Ok(Counts {
+            vertices: sum(&[self.vertices, selection.count])?,
+            edges: sum(&[self.edges, selection.total])?,
+            faces: sum(&[self.faces - selection.count, selection.total])?,
+            vertex_degrees,
+            face_sides,
+            is_exact: self.is_exact && selection.is_exact,
+            is_overflowed: false,
+        })
+    }
+
+    /// Truncation is the dual of kis, cutting off the vertices that kis would add to the faces
+    /// of the dual.
+    fn truncate(&self, selector: VertexSelector) -> Checked<Counts> {
+        let dual = self.dual();
+        let selection = Selection::by_count(
+            &dual.face_sides,
+            selector.degree(),
+            dual.faces,
+            product(2, dual.edges)?,
+        )?;
+        Ok(dual.checked_kis(selection)?.dual())
+    }
+
+    fn gyro(&self) -> Checked<Counts> {
+        let new_vertices = product(2, self.edges)?;
+        let vertex_degrees = union(&self.vertex_degrees, &self.face_sides)?;
+        Ok(Counts {
+            vertices: sum(&[self.vertices, self.faces, new_vertices])?,
+            edges: product(5, self.edges)?,
+            faces: new_vertices,
+            vertex_degrees: vertex_degrees.map(|d| plus(d, 3, new_vertices)).transpose()?,
+            face_sides: Some(single(5, new_vertices)),
+            is_exact: self.is_exact,
+            is_overflowed: false,
+        })
+    }
+
+    /// Adds the given numbers of vertices, edges and faces for every edge, where the vertices and
+    /// faces have the given degree and sides. The original vertices and faces keep their degrees
+    /// and sides.
+    fn per_edge(
+        &self,
+        vertices: (usize, u32),
+        edges: usize,
+        faces: (usize, u32),
+    ) -> Checked<Counts> {
+        let (vertex_count, degree) = vertices;
+        let (face_count, sides) = faces;
+        let new_vertices = product(vertex_count, self.edges)?;
+        let new_faces = product(face_count, self.edges)?;
+        Ok(Counts {
+            vertices: sum(&[self.vertices, new_vertices])?,
+            edges: product(1 + edges, self.edges)?,
+            faces: sum(&[self.faces, new_faces])?,
+            vertex_degrees: self
+                .vertex_degrees
+                .clone()
+                .map(|degrees| plus(degrees, degree, new_vertices))
+                .transpose()?,
+            face_sides: self
+                .face_sides
+                .clone()
+                .map(|face_sides| plus(face_sides, sides, new_faces))
+                .transpose()?,
+            is_exact: self.is_exact,
+            is_overflowed: false,
+        })
+    }
+
+    /// Predicts the counts of `loft`, `inset` and `extrude`, which surround each selected face
+    /// with a ring of quadrilaterals.
+    fn augment(&self, selection: Selection) -> Checked<Counts> {
+        let vertex_degrees = self.scale_degrees(&selection, 2, None)?;
+        Ok(Counts {
+            vertices: sum(&[self.vertices, selection.total])?,
+            edges: sum(&[self.edges, product(2, selection.total)?])?,
+            faces: sum(&[self.faces, selection.total])?,
+            vertex_degrees: vertex_degrees.map(|d| plus(d, 3, selection.total)).transpose()?,
+            face_sides: self
+                .face_sides
+                .clone()
+                .filter(|_| selection.is_exact)
+                .map(|face_sides| plus(face_sides, 4, selection.total))
+                .transpose()?,
+            is_exact: self.is_exact && selection.is_exact,
+            is_overflowed: false,
+        })
+    }
+
+    fn lace(&self, selection: Selection) -> Checked<Counts> {
+        let vertex_degrees = self.scale_degrees(&selection, 3, None)?;
+        let new_faces = product(2, selection.total)?;
+        Ok(Counts {
+            vertices: sum(&[self.vertices, selection.total])?,
+            edges: sum(&[self.edges, product(3, selection.total)?])?,
+            faces: sum(&[self.faces, new_faces])?,
+            vertex_degrees: vertex_degrees.map(|d| plus(d, 4, selection.total)).transpose()?,
+            face_sides: self
+                .face_sides
+                .clone()
+                .filter(|_| selection.is_exact)
+                .map(|face_sides| plus(face_sides, 3, new_faces))
+                .transpose()?,
+            is_exact: self.is_exact && selection.is_exact,
+            is_overflowed: false,
+        })
+    }
+
+    fn stake(&self, selection: Selection) -> Checked<Counts> {
+        let vertex_degrees = self.scale_degrees(&selection, 3, selection.histogram.as_ref())?;
+        let face_sides = match selection.remainder(&self.face_sides) {
+            Some(remainder) => {
+                let triangles = plus(remainder, 3, selection.total)?;
+                Some(plus(triangles, 4, selection.total)?)
+            },
+            None => None,
+        };
+        Ok(Counts {
+            vertices: sum(&[self.vertices, selection.count, selection.total])?,
+            edges: sum(&[self.edges, product(3, selection.total)?])?,
+            faces: sum(&[self.faces - selection.count, product(2, selection.total)?])?,
+            vertex_degrees: vertex_degrees.map(|d| plus(d, 3, selection.total)).transpose()?,
+            face_sides,
+            is_exact: self.is_exact && selection.is_exact,
+            is_overflowed: false,
+        })
+    }
+
+    /// Returns the vertex degrees if every face is selected, where each original vertex has its
+    /// degree multiplied by `factor`, and each selected face may add a vertex with as many edges
+    /// as it has sides. Returns the degrees unchanged if no face is selected.
+    fn scale_degrees(
+        &self,
+        selection: &Selection,
+        factor: u32,
+        centers: Option<&Histogram>,
+    ) -> Checked<Option<Histogram>> {
+        if selection.count == 0 {
+            return Ok(self.vertex_degrees.clone());
+        }
+        let degrees = match &self.vertex_degrees {
+            Some(degrees) if selection.is_all => scaled(degrees, factor)?,
+            _ => return Ok(None),
+        };
+        match centers {
+            Some(centers) => union(&Some(degrees), &Some(centers.clone())),
+            None => Ok(Some(degrees)),
+        }
+    }
+
+    /// Predicts the counts of `quinto`, `joined-lace` and `cross`, whose new faces surround each
+    /// edge of the selected faces. Selecting only some faces changes the faces beside them, so
+    /// only selecting every face or none is exact.
+    fn per_edge_of_faces(
+        &self,
+        selection: Selection,
+        transform: impl Fn(&Counts) -> Checked<Counts>,
+    ) -> Checked<Counts> {
+        if selection.count == 0 {
+            return Ok(self.clone());
+        }
+        let mut counts = transform(self)?;
+        if !(selection.is_all && selection.is_exact) {
+            counts.vertex_degrees = None;
+            counts.face_sides = None;
+            counts.is_exact = false;
+        }
+        Ok(counts)
+    }
+
+    fn quinto(&self) -> Checked<Counts> {
+        let mut counts = self.per_edge((2, 3), 5, (2, 5))?;
+        counts.vertices = sum(&[counts.vertices, self.edges])?;
+        counts.vertex_degrees =
+            counts.vertex_degrees.map(|d| plus(d, 4, self.edges)).transpose()?;
+        Ok(counts)
+    }
+
+    fn joined_lace(&self) -> Checked<Counts> {
+        let new_vertices = product(2, self.edges)?;
+        let vertex_degrees = match &self.vertex_degrees {
+            Some(degrees) => Some(plus(scaled(degrees, 2)?, 4, new_vertices)?),
+            None => None,
+        };
+        let face_sides = match self.face_sides.clone() {
+            Some(face_sides) => Some(plus(plus(face_sides, 3, new_vertices)?, 4, self.edges)?),
+            None => None,
+        };
+        Ok(Counts {
+            vertices: sum(&[self.vertices, new_vertices])?,
+            edges: product(6, self.edges)?,
+            faces: sum(&[self.faces, product(3, self.edges)?])?,
+            vertex_degrees,
+            face_sides,
+            is_exact: self.is_exact,
+            is_overflowed: false,
+        })
+    }
+
+    fn cross(&self) -> Checked<Counts> {
+        let new_vertices = product(3, self.edges)?;
+        let vertex_degrees = self.vertex_degrees.as_ref().map(|d| scaled(d, 3)).transpose()?;
+        let vertex_degrees = union(&vertex_degrees, &self.face_sides)?;
+        Ok(Counts {
+            vertices: sum(&[self.vertices, self.faces, new_vertices])?,
+            edges: product(10, self.edges)?,
+            faces: product(6, self.edges)?,
+            vertex_degrees: vertex_degrees.map(|d| plus(d, 4, new_vertices)).transpose()?,
+            face_sides: Some(plus(single(3, product(4, self.edges)?), 4, product(2, self.edges)?)?),
+            is_exact: self.is_exact,
+            is_overflowed: false,
+        })
+    }
+
+    fn goldberg_coxeter(&self, goldberg_coxeter: GoldbergCoxeter) -> Checked<Counts> {
+        let (m, n) = (goldberg_coxeter.m() as usize, goldberg_coxeter.n() as usize);
+        let is_square = match &self.face_sides {
+            Some(face_sides) => face_sides.keys().all(|&sides| sides == 4),
+            None => false,
+        };
+        let (triangulated, frequency, degree) = if is_square {
+            (self.clone(), sum(&[product(m, m)?, product(n, n)?])?, 4)
+        } else {
+            // Faces with more than three sides are first divided into triangles.
+            let selection = match &self.face_sides {
+                Some(face_sides) => Selection::by_histogram(
+                    face_sides.iter().filter(|(&sides, _)| sides > 3),
+                    self.faces,
+                )?,
+                None => Selection::everything(self.faces, product(2, self.edges)?, false),
+            };
+            let frequency = sum(&[product(m, m)?, product(m, n)?, product(n, n)?])?;
+            (self.checked_kis(selection)?, frequency, 6)
+        };
+
+        let added = product(frequency - 1, triangulated.edges - triangulated.faces)?;
+        let sides = if is_square { 4 } else { 3 };
+        let faces = product(frequency, triangulated.faces)?;
+        Ok(Counts {
+            vertices: sum(&[triangulated.vertices, added])?,
+            edges: product(frequency, triangulated.edges)?,
+            faces,
+            vertex_degrees: triangulated
+                .vertex_degrees
+                .map(|degrees| plus(degrees, degree, added))
+                .transpose()?,
+            face_sides: Some(single(sides, faces)),
+            is_exact: triangulated.is_exact,
+            is_overflowed: false,
+        })
+    }
+
+    fn select_faces(&self, selector: &FaceSelector) -> Checked<Selection> {
+        let side_count = selector.side_count();
+        let total = product(2, self.edges)?;
+        let selection = Selection::by_count(&self.face_sides, side_count, self.faces, total)?;
+        if *selector == FaceSelector::default().with_sides(side_count) {
+            Ok(selection)
+        } else {
+            Ok(selection.into_upper_bound())
+        }
+    }
+}
+
+impl Operator {
+    /// Predicts the counts of the polyhedron this operator produces from one with the given
+    /// counts.
+    pub fn transform_counts(&self, counts: &Counts) -> Counts {
+        counts.transform(|counts| self.checked_transform_counts(counts))
+    }
+
+    fn checked_transform_counts(&self, counts: &Counts) -> Checked<Counts> {
+        let counts = match self {
+            Operator::Ambo => Ambo.transform_counts(counts),
+            Operator::Dual => Dual.transform_counts(counts),
+            Operator::Kis(kis) => kis.transform_counts(counts),
+            Operator::Truncate(truncate) => counts.truncate(truncate.selector())?,
+            Operator::Join => counts.checked_ambo()?.dual(),
+            Operator::Ortho => counts.checked_ambo()?.dual().checked_ambo()?.dual(),
+            Operator::Expand => counts.checked_ambo()?.checked_ambo()?,
+            Operator::Gyro(_) => counts.gyro()?,
+            Operator::Snub(_) => counts.dual().gyro()?.dual(),
+            Operator::Bevel => counts.checked_ambo()?.truncate(VertexSelector::default())?,
+            Operator::Meta => {
+                let join = counts.checked_ambo()?.dual();
+                join.checked_kis(join.select_faces(&FaceSelector::default())?)?
+            },
+            Operator::Needle(needle) => {
+                let dual = counts.dual();
+                let selector = FaceSelector::default().with_sides(needle.side_count());
+                dual.checked_kis(dual.select_faces(&selector)?)?
+            },
+            Operator::Zip(zip) => counts.checked_kis(counts.select_faces(zip.selector())?)?.dual(),
+            Operator::Propeller(_) => counts.per_edge((2, 4), 4, (2, 4))?,
+            Operator::Reflect => counts.clone(),
+            Operator::Chamfer(chamfer) => {
+                let mut chamfered = counts.per_edge((2, 3), 3, (1, 6))?;
+                if chamfer.selector() != EdgeSelector::default() {
+                    chamfered.vertex_degrees = None;
+                    chamfered.face_sides = None;
+                    chamfered.is_exact = false;
+                }
+                chamfered
+            },
+            Operator::Whirl(_) => counts.per_edge((4, 3), 6, (2, 6))?,
+            Operator::Loft(loft) => counts.augment(counts.select_faces(loft.selector())?)?,
+            Operator::Inset(inset) => counts.augment(counts.select_faces(inset.selector())?)?,
+            Operator::Extrude(extrude) => {
+                counts.augment(counts.select_faces(extrude.selector())?)?
+            },
+            Operator::Lace(lace) => counts.lace(counts.select_faces(lace.selector())?)?,
+            Operator::Stake(stake) => counts.stake(counts.select_faces(stake.selector())?)?,
+            Operator::Quinto(quinto) => {
+                let selection = counts.select_faces(quinto.selector())?;
+                counts.per_edge_of_faces(selection, Counts::quinto)?
+            },
+            Operator::JoinedLace(joined_lace) => {
+                let selection = counts.select_faces(joined_lace.selector())?;
+                counts.per_edge_of_faces(selection, Counts::joined_lace)?
+            },
+            Operator::Cross(cross) => {
+                let selection = counts.select_faces(cross.selector())?;
+                counts.per_edge_of_faces(selection, Counts::cross)?
+            },
+            Operator::GoldbergCoxeter(goldberg_coxeter) => {
+                counts.goldberg_coxeter(*goldberg_coxeter)?
+            },
+            Operator::Custom(custom) => custom.operator().transform_counts(counts),
+        };
+        // Operators built through `ConwayOperator` report overflow in the counts they return
+        if counts.is_overflowed {
+            return Err(Overflow);
+        }
+        Ok(counts)
+    }
+}
+
+/// The faces or vertices an operator acts upon.
+struct Selection {
+    /// The number selected with each side count or degree, if known.
+    histogram: Option<Histogram>,
+    count: usize,
+    /// The total number of sides of the selected faces, or edges of the selected vertices.
+    total: usize,
+    is_all: bool,
+    is_exact: bool,
+}
+
+impl Selection {
+    /// Selects the faces with the given number of sides, or vertices with the given degree,
+    /// selecting everything if `side_count` is zero.
+    fn by_count(
+        histogram: &Option<Histogram>,
+        side_count: u32,
+        count: usize,
+        total: usize,
+    ) -> Checked<Selection> {
+        let selection = match histogram {
+            _ if side_count == 0 => Selection {
+                histogram: histogram.clone(),
+                ..Selection::everything(count, total, true)
+            },
+            Some(histogram) => Selection::by_histogram(
+                histogram.iter().filter(|(&sides, _)| sides == side_count),
+                count,
+            )?,
+            None => Selection::everything(count, total, false),
+        };
+        Ok(selection)
+    }
+
+    fn by_histogram<'a>(
+        selected: impl Iterator<Item = (&'a u32, &'a usize)>,
+        out_of: usize,
+    ) -> Checked<Self> {
+        let histogram: Histogram = selected.map(|(&key, &count)| (key, count)).collect();
+        let count = sum(&histogram.values().cloned().collect::<Vec<_>>())?;
+        Ok(Selection {
+            total: total(&histogram)?,
+            histogram: Some(histogram),
+            count,
+            is_all: count == out_of,
+            is_exact: true,
+        })
+    }
+
+    fn everything(count: usize, total: usize, is_exact: bool) -> Self {
+        Selection {
+            histogram: None,
+            count,
+            total,
+            is_all: is_exact,
+            is_exact,
+        }
+    }
+
+    /// Treats the selection as the most that could be selected, for selectors with criteria
+    /// beyond the number of sides.
+    fn into_upper_bound(self) -> Self {
+        Selection {
+            histogram: None,
+            is_all: false,
+            is_exact: self.count == 0,
+            ..self
+        }
+    }
+
+    /// Returns the histogram without the selected elements, if both are known.
+    fn remainder(&self, histogram: &Option<Histogram>) -> Option<Histogram> {
+        let mut remainder = histogram.clone()?;
+        if self.is_all {
+            return Some(Histogram::new());
+        }
+        for (key, count) in self.histogram.as_ref()? {
+            let entry = remainder.get_mut(key).unwrap();
+            *entry -= count;
+            if *entry == 0 {
+                remainder.remove(key);
+            }
+        }
+        Some(remainder)
+    }
+}
+
+/// Adds counts, failing if the sum overflows.
+fn sum(counts: &[usize]) -> Checked<usize> {
+    counts.iter().try_fold(0usize, |sum, &count| sum.checked_add(count)).ok_or(Overflow)
+}
+
+/// Multiplies a count by a factor, failing if the product overflows.
+fn product(factor: usize, count: usize) -> Checked<usize> {
+    factor.checked_mul(count).ok_or(Overflow)
+}
+
+fn add(histogram: &mut Histogram, key: u32, count: usize) -> Checked<()> {
+    if count > 0 {
+        let entry = histogram.entry(key).or_insert(0);
+        *entry = sum(&[*entry, count])?;
+    }
+    Ok(())
+}
+
+fn plus(mut histogram: Histogram, key: u32, count: usize) -> Checked<Histogram> {
+    add(&mut histogram, key, count)?;
+    Ok(histogram)
+}
+
+fn single(key: u32, count: usize) -> Histogram {
+    let mut histogram = Histogram::new();
+    if count > 0 {
+        histogram.insert(key, count);
+    }
+    histogram
+}
+
+/// Multiplies each degree or number of sides by the factor, failing if any overflows.
+fn scaled(histogram: &Histogram, factor: u32) -> Checked<Histogram> {
+    histogram
+        .iter()
+        .map(|(&key, &count)| Ok((key.checked_mul(factor).ok_or(Overflow)?, count)))
+        .collect()
+}
+
+/// Combines the histograms, which is unknown if either of them is.
+fn union(first: &Option<Histogram>, second: &Option<Histogram>) -> Checked<Option<Histogram>> {
+    let (mut union, second) = match (first, second) {
+        (Some(first), Some(second)) => (first.clone(), second),
+        _ => return Ok(None),
+    };
+    for (&key, &count) in second {
+        add(&mut union, key, count)?;
+    }
+    Ok(Some(union))
+}
+
+/// Returns the total number of sides or edges counted by the histogram.
+fn total(histogram: &Histogram) -> Checked<usize> {
+    histogram.iter().try_fold(0usize, |total, (&key, &count)| {
+        sum(&[total, product(key as usize, count)?])
+    })
+}
+
+/// The most edges a polyhedron may be built with. Edges are limited since every polyhedron has
+/// more edges than it has vertices or faces.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct SizeLimit {
+    edges: Option<usize>,
+}
+
+impl SizeLimit {
+    /// The limit used unless another is given, enough for around a third as many faces.
+    pub const DEFAULT_EDGES: usize = 1_000_000;
+
+    /// Limits polyhedra to the given number of edges.
+    pub fn edges(edges: usize) -> Self {
+        SizeLimit {
+            edges: Some(edges),
+        }
+    }
+
+    /// Allows polyhedra with any number of edges, as long as it can be counted.
+    pub fn unlimited() -> Self {
+        SizeLimit {
+            edges: None,
+        }
+    }
+
+    /// Returns the most edges a polyhedron may have, or `None` if there is no limit.
+    pub fn maximum_edges(&self) -> Option<usize> {
+        self.edges
+    }
+
+    /// Returns an error if the counts exceed the limit, or overflowed.
+    pub fn check(&self, counts: &Counts) -> Result<(), SizeError> {
+        let is_too_large = match self.edges {
+            _ if counts.is_overflowed => true,
+            Some(limit) => counts.edges > limit,
+            None => false,
+        };
+        if is_too_large {
+            return Err(SizeError {
+                counts: counts.clone(),
+                limit: self.edges,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for SizeLimit {
+    fn default() -> Self {
+        SizeLimit::edges(SizeLimit::DEFAULT_EDGES)
+    }
+}
+
+/// An error from building a polyhedron predicted to exceed the size limit.
+#[derive(Clone, Debug)]
+pub struct SizeError {
+    counts: Counts,
+    limit: Option<usize>,
+}
+
+impl SizeError {
+    /// The predicted counts of the polyhedron that was refused.
+    pub fn counts(&self) -> &Counts {
+        &self.counts
+    }
+
+    /// The most edges that were allowed, or `None` if there was no limit and the counts
+    /// overflowed.
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+}
+
+impl std::fmt::Display for SizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.counts.is_overflowed {
+            return f.write_str("the polyhedron would have too many edges to count");
+        }
+        write!(
+            f,
+            "the polyhedron would have {}{} edges, more than the limit of {}",
+            if self.counts.is_exact { "" } else { "up to " },
+            self.counts.edges,
+            self.limit.unwrap_or(usize::MAX)
+        )
+    }
+}
+
+impl std::error::Error for SizeError {}
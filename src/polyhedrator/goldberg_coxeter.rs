@@ -0,0 +1,298 @@
+use super::builder::Builder;
+use super::keys::{FaceKey, VertexKey};
+use super::operators::GoldbergCoxeter;
+use super::progress::Progress;
+use super::{center, Polyhedron, Vertex};
+use fnv::FnvHashMap;
+
+impl Polyhedron {
+    /// Applies the Goldberg-Coxeter operator and returns the resulting polyhedron.
+    pub fn goldberg_coxeter(self, goldberg_coxeter: GoldbergCoxeter) -> Polyhedron {
+        self.build_goldberg_coxeter(goldberg_coxeter, &Progress::default())
+    }
+
+    pub(super) fn build_goldberg_coxeter(
+        self,
+        goldberg_coxeter: GoldbergCoxeter,
+        progress: &Progress,
+    ) -> Polyhedron {
+        let chirality = self.chirality.or_else(|| goldberg_coxeter.chirality());
+        let polyhedron = if self.faces.iter().all(|face| face.indices.len() == 4) {
+            self
+        }
+        else {
+            self.triangulate(progress)
+        };
+        // A cancelled triangulation is missing faces, so the lattice cannot be laid over it
+        if progress.is_cancelled() {
+            return polyhedron;
+        }
+        let lattice = Lattice::new(&polyhedron, goldberg_coxeter);
+        let (m, n) = (lattice.m, lattice.n);
+        let mut builder = Builder::with_progress(progress);
+
+        for face_index in 0..polyhedron.faces.len() {
+            // Each cell is owned by the face containing its center, so cells straddling an edge
+            // are only added once. The lattice points of such cells are looked up in the
+            // neighboring face, so that they share keys.
+            for x in -n - 1..=m {
+                for y in -1..=m + n {
+                    for cell in lattice.cells(x, y) {
+                        if !lattice.owns(face_index, cell.center, cell.scale) {
+                            continue;
+                        }
+
+                        let face_key = FaceKey::Lattice(face_index as u32, x, y, cell.index);
+                        let keys: Vec<_> = cell
+                            .corners
+                            .iter()
+                            .map(|corner| {
+                                let (key, position) = lattice.locate(face_index, *corner);
+                                builder.add_vertex(key, position);
+                                key
+                            })
+                            .collect();
+
+                        let mut v1_key = *keys.last().unwrap();
+                        for v2_key in keys {
+                            builder.add_flag(face_key, v1_key, v2_key);
+                            v1_key = v2_key;
+                        }
+                    }
+                }
+            }
+        }
+
+        builder.build_polyhedron(chirality, &polyhedron.seed_faces)
+    }
+
+    /// Divides each face that is not a triangle into triangles meeting at its center.
+    fn triangulate(self, progress: &Progress) -> Polyhedron {
+        let mut builder = Builder::with_progress(progress);
+
+        for i in 0..self.vertices.len() {
+            let vertex = self.vertices[i];
+            builder.add_vertex(VertexKey::Seed(i as u32), vertex);
+        }
+
+        for face_index in 0..self.faces.len() {
+            let face = &self.faces[face_index];
+            let face_index = face_index as u32;
+            let is_triangle = face.indices.len() == 3;
+            let center_key = VertexKey::Centroid(face_index);
+            if !is_triangle {
+                builder.add_vertex(center_key, center(self.face_vertices(face)));
+            }
+
+            let mut v1_key = VertexKey::Seed(*face.indices.last().unwrap());
+            for index in &face.indices {
+                let v2_key = VertexKey::Seed(*index);
+                if is_triangle {
+                    builder.add_flag(FaceKey::Seed(face_index), v1_key, v2_key);
+                }
+                else {
+                    let face_key = FaceKey::PyramidFace(face_index, v1_key);
+                    builder.add_flag(face_key, v1_key, v2_key);
+                    builder.add_flag(face_key, v2_key, center_key);
+                    builder.add_flag(face_key, center_key, v1_key);
+                }
+                v1_key = v2_key;
+            }
+        }
+
+        builder.build_polyhedron(self.chirality, &self.seed_faces)
+    }
+}
+
+/// The triangular or square lattice laid over every face of a polyhedron.
+///
+/// Points are given relative to a frame, which places one vertex of the face at the origin and
+/// the next vertex at `(total, 0)`; the third coordinate axis runs along the previous edge for
+/// square lattices, or towards the remaining vertex for triangular lattices. Each face's own
+/// coordinates are relative to the frame at its first vertex.
+struct Lattice<'a> {
+    polyhedron: &'a Polyhedron,
+    edge_faces: Vec<FnvHashMap<u32, u32>>,
+    is_square: bool,
+    m: i64,
+    n: i64,
+    total: i64,
+}
+
+/// A triangle or square of the lattice, given by its lattice points in counterclockwise order.
+struct Cell {
+    index: u8,
+    corners: Vec<(i64, i64)>,
+    /// The center of the cell, multiplied by `scale` so that it lies on the lattice.
+    center: (i64, i64),
+    scale: i64,
+}
+
+impl<'a> Lattice<'a> {
+    fn new(polyhedron: &'a Polyhedron, goldberg_coxeter: GoldbergCoxeter) -> Self {
+        let is_square = polyhedron.faces.iter().all(|face| face.indices.len() == 4);
+        let m = goldberg_coxeter.m() as i64;
+        let n = goldberg_coxeter.n() as i64;
+        let total = if is_square {
+            m * m + n * n
+        }
+        else {
+            m * m + m * n + n * n
+        };
+
+        Lattice {
+            polyhedron,
+            edge_faces: polyhedron.edge_faces(),
+            is_square,
+            m,
+            n,
+            total,
+        }
+    }
+
+    /// Returns the cells whose lowest lattice point is `(x, y)`.
+    fn cells(&self, x: i64, y: i64) -> Vec<Cell> {
+        if self.is_square {
+            vec![Cell {
+                index: 0,
+                corners: vec![(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)],
+                center: (2 * x + 1, 2 * y + 1),
+                scale: 2,
+            }]
+        }
+        else {
+            vec![
+                Cell {
+                    index: 0,
+                    corners: vec![(x, y), (x + 1, y), (x, y + 1)],
+                    center: (3 * x + 1, 3 * y + 1),
+                    scale: 3,
+                },
+                Cell {
+                    index: 1,
+                    corners: vec![(x + 1, y), (x + 1, y + 1), (x, y + 1)],
+                    center: (3 * x + 2, 3 * y + 2),
+                    scale: 3,
+                },
+            ]
+        }
+    }
+
+    /// Converts a lattice point into the coordinates of the face's first frame.
+    fn coordinates(&self, (x, y): (i64, i64)) -> (i64, i64) {
+        let (m, n) = (self.m, self.n);
+        if self.is_square {
+            (x * m + y * n, y * m - x * n)
+        }
+        else {
+            (x * (m + n) + y * n, y * m - x * n)
+        }
+    }
+
+    /// Converts coordinates into the frame at the next vertex of the face.
+    fn rotate(&self, (a, b): (i64, i64), total: i64) -> (i64, i64) {
+        if self.is_square {
+            (b, total - a)
+        }
+        else {
+            (b, total - a - b)
+        }
+    }
+
+    /// Converts coordinates into the frame at the previous vertex of the face.
+    fn rotate_back(&self, (a, b): (i64, i64), total: i64) -> (i64, i64) {
+        if self.is_square {
+            (total - b, a)
+        }
+        else {
+            (total - a - b, a)
+        }
+    }
+
+    /// Returns whether the point lies inside the face, or on an edge running from a lower vertex
+    /// index to a higher one. Every point belongs to exactly one face this way, except the
+    /// vertices of the polyhedron.
+    fn owns(&self, face_index: usize, point: (i64, i64), scale: i64) -> bool {
+        let indices = &self.polyhedron.faces[face_index].indices;
+        let total = self.total * scale;
+        let mut coordinates = self.coordinates(point);
+        for i in 0..indices.len() {
+            let (_, b) = coordinates;
+            let next = indices[(i + 1) % indices.len()];
+            if b < 0 || (b == 0 && indices[i] > next) {
+                return false;
+            }
+            coordinates = self.rotate(coordinates, total);
+        }
+        true
+    }
+
+    /// Returns the key and position of a lattice point, which may lie outside the face, but no
+    /// further than the faces sharing its edges.
+    fn locate(&self, face_index: usize, point: (i64, i64)) -> (VertexKey, Vertex) {
+        let mut face_index = face_index;
+        let mut coordinates = self.coordinates(point);
+        'faces: loop {
+            let indices = &self.polyhedron.faces[face_index].indices;
+            let sides = indices.len();
+            for i in 0..sides {
+                let (a, b) = coordinates;
+                let v1 = indices[i];
+                let v2 = indices[(i + 1) % sides];
+                if b < 0 {
+                    // Cross over the edge into the frame at `v2` in the neighboring face, then
+                    // return to that face's first frame.
+                    let neighbor = self.edge_faces[v2 as usize][&v1] as usize;
+                    let neighbor_indices = &self.polyhedron.faces[neighbor].indices;
+                    let frame = neighbor_indices.iter().position(|index| *index == v2).unwrap();
+                    coordinates = (self.total - a, -b);
+                    for _ in 0..frame {
+                        coordinates = self.rotate_back(coordinates, self.total);
+                    }
+                    face_index = neighbor;
+                    continue 'faces;
+                }
+                if b == 0 && a >= 0 && a <= self.total {
+                    let key = if a == 0 {
+                        VertexKey::Seed(v1)
+                    }
+                    else if a == self.total {
+                        VertexKey::Seed(v2)
+                    }
+                    else if v1 < v2 {
+                        VertexKey::EdgeLattice(v1, v2, a)
+                    }
+                    else {
+                        VertexKey::EdgeLattice(v2, v1, self.total - a)
+                    };
+                    return (key, self.position(face_index, i, coordinates));
+                }
+                coordinates = self.rotate(coordinates, self.total);
+            }
+
+            let (a, b) = coordinates;
+            let key = VertexKey::Lattice(face_index as u32, a, b);
+            return (key, self.position(face_index, 0, coordinates));
+        }
+    }
+
+    /// Interpolates the position of a point inside the face, given in the frame at the given
+    /// vertex.
+    fn position(&self, face_index: usize, frame: usize, (a, b): (i64, i64)) -> Vertex {
+        let indices = &self.polyhedron.faces[face_index].indices;
+        let vertex = |i: usize| {
+            let index = indices[(frame + i) % indices.len()];
+            self.polyhedron.vertices[index as usize]
+        };
+        let s = a as f64 / self.total as f64;
+        let t = b as f64 / self.total as f64;
+        let origin = vertex(0);
+        if self.is_square {
+            let bilinear = origin - vertex(1) + (vertex(2) - vertex(3));
+            origin + (vertex(1) - origin) * s + (vertex(3) - origin) * t + bilinear * (s * t)
+        }
+        else {
+            origin + (vertex(1) - origin) * s + (vertex(2) - origin) * t
+        }
+    }
+}
@@ -1,8 +1,24 @@
+/// Names a vertex being built by what it is built from, using the indices of the vertices and
+/// faces of the original polyhedron.
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
 pub enum VertexKey {
     Seed(u32),
     Midpoint(u32, u32),
     Centroid(u32),
+    /// A vertex belonging to the edge running from the first seed vertex to the second, such as a
+    /// point along it nearer the first vertex, or a point inside the face that edge borders.
+    EdgePoint(u32, u32),
+    /// A vertex inside the given seed face, nearest the given seed vertex.
+    Corner(u32, u32),
+    /// A vertex inside the given seed face, nearest the given seed vertex along the edge to the
+    /// last seed vertex.
+    Flag(u32, u32, u32),
+    /// A lattice point strictly inside the given seed face, given by its weights relative to the
+    /// second and third vertices of the face.
+    Lattice(u32, i64, i64),
+    /// A lattice point along the edge between the given seed vertices, given by its weight
+    /// relative to the first vertex, which is always the smaller of the two.
+    EdgeLattice(u32, u32, i64),
 }
 
 impl VertexKey {
@@ -15,9 +31,51 @@ impl VertexKey {
     }
 }
 
+/// Names a face being built by what it is built from, using the indices of the vertices and faces
+/// of the original polyhedron. Faces built within a face of the original, as given by
+/// [`FaceKey::parent_face`], descend from the same seed face.
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
 pub enum FaceKey {
     Seed(u32),
     Vertex(u32),
     PyramidFace(u32, VertexKey),
+    Edge(u32, u32),
+    /// A face inside the given seed face, attached to the given seed vertex.
+    Corner(u32, u32),
+    /// A face surrounding the vertex with the matching `VertexKey::EdgePoint`.
+    EdgePoint(u32, u32),
+    /// A face inside the given seed face, attached to the given seed vertex along the edge to the
+    /// last seed vertex.
+    Flag(u32, u32, u32),
+    /// A lattice cell owned by the given seed face, given by its lattice coordinates and which of
+    /// the cells at those coordinates it is.
+    Lattice(u32, i64, i64, u8),
+    /// A face of the pyramid raised over the given seed vertex, bordering the given vertex.
+    VertexPyramidFace(u32, VertexKey),
+}
+
+impl FaceKey {
+    pub fn edge(first: u32, second: u32) -> FaceKey {
+        if first < second {
+            FaceKey::Edge(first, second)
+        } else {
+            FaceKey::Edge(second, first)
+        }
+    }
+
+    /// Returns the index of the seed face this face lies within, or `None` if it was created from
+    /// a seed vertex or edge.
+    pub fn parent_face(&self) -> Option<u32> {
+        match *self {
+            FaceKey::Seed(face)
+            | FaceKey::PyramidFace(face, _)
+            | FaceKey::Corner(face, _)
+            | FaceKey::Flag(face, _, _)
+            | FaceKey::Lattice(face, _, _, _) => Some(face),
+            FaceKey::Vertex(_)
+            | FaceKey::Edge(_, _)
+            | FaceKey::EdgePoint(_, _)
+            | FaceKey::VertexPyramidFace(_, _) => None,
+        }
+    }
 }
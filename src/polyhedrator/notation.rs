@@ -0,0 +1,839 @@
+mod conway;
+
+use super::operators::{parse_integer, parse_operator, parse_verbose_operator, Operator};
+use super::seeds::{Platonic, Seed};
+use super::simplify::{simplify, Rewrite};
+use super::counts::{Counts, SizeError, SizeLimit};
+use super::progress::{BuildError, CancellationToken, Observer};
+use super::{ConwayOperator, CustomOperator};
+use super::Polyhedron;
+use fnv::FnvHashMap;
+use pest::iterators::{Pair, Pairs};
+use pest_derive::Parser;
+
+#[derive(Parser)]
+#[grammar = "polyhedrator/notation.pest"]
+pub(super) struct NotationParser;
+
+/// A polyhedron written in Conway notation: a seed, and the operators applied to it. Operators
+/// are kept in the order they are written, and so are applied right to left.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Notation {
+    seed: Seed,
+    operators: Vec<Operator>,
+}
+
+impl Notation {
+    pub fn new(seed: Seed, operators: Vec<Operator>) -> Self {
+        Notation {
+            seed,
+            operators,
+        }
+    }
+
+    pub fn seed(&self) -> Seed {
+        self.seed
+    }
+
+    pub fn operators(&self) -> &[Operator] {
+        &self.operators
+    }
+
+    /// Writes the notation with the given options. `Display` uses the default options.
+    pub fn print(&self, options: PrintOptions) -> String {
+        let mut notation = print_operators(&self.operators, options);
+        match options.dialect {
+            Dialect::Compact => notation.push_str(&compact_seed(self.seed)),
+            Dialect::Verbose => {
+                if !notation.is_empty() {
+                    notation.push(' ');
+                }
+                notation.push_str(&verbose_seed(self.seed));
+            },
+        }
+        notation
+    }
+
+    /// Builds the seed with unit edge length, and applies each operator to it.
+    pub fn build(&self) -> Polyhedron {
+        let seed = self.seed.polyhedron(1.0);
+        seed.apply_iter(self.operators.iter().rev().cloned())
+    }
+
+    /// Builds the notation with the given options. Returns an error if the result is predicted to
+    /// exceed the size limit, which is a million edges by default.
+    pub fn build_with(&self, options: BuildOptions) -> Result<Polyhedron, SizeError> {
+        let seed = self.seed.polyhedron(1.0);
+        seed.apply_iter_with_limit(self.applied_operators(options), options.limit)
+    }
+
+    /// Builds the notation with the given options, reporting the progress of the build to the
+    /// observer and stopping if the token is cancelled. Operators are reported in the order they
+    /// are applied, so the first is the last written.
+    pub fn build_observed(
+        &self,
+        options: BuildOptions,
+        observer: impl Observer + 'static,
+        token: &CancellationToken,
+    ) -> Result<Polyhedron, BuildError> {
+        let seed = self.seed.polyhedron(1.0);
+        seed.apply_iter_observed(self.applied_operators(options), options.limit, observer, token)
+    }
+
+    /// The operators to apply with the given options, in the order they are applied.
+    fn applied_operators(&self, options: BuildOptions) -> Vec<Operator> {
+        let mut operators = if options.simplify {
+            simplify(&self.operators).into_operators()
+        }
+        else {
+            self.operators.clone()
+        };
+        operators.reverse();
+        operators
+    }
+
+    /// Predicts the counts of the polyhedron the notation builds, without building it.
+    pub fn counts(&self) -> Counts {
+        self.seed.counts().apply_iter(self.operators.iter().rev())
+    }
+
+    /// Shortens the operators using identities between them, such as `dd = identity`, returning
+    /// the simplified notation along with each rewrite made.
+    pub fn simplify(&self) -> (Notation, Vec<Rewrite>) {
+        let simplification = simplify(&self.operators);
+        let rewrites = simplification.rewrites().to_vec();
+        (Notation::new(self.seed, simplification.into_operators()), rewrites)
+    }
+}
+
+impl std::str::FromStr for Notation {
+    type Err = NotationError;
+
+    /// Parses a notation, which may only use the macros it defines itself.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        NotationContext::default().parse_notation(value)
+    }
+}
+
+impl std::fmt::Display for Notation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.print(PrintOptions::default()))
+    }
+}
+
+/// Options controlling how operators are written as notation.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct PrintOptions {
+    compress: bool,
+    dialect: Dialect,
+}
+
+impl PrintOptions {
+    /// Sets whether consecutive repetitions of the same operators are written as a group, such as
+    /// `(dk)^4` instead of `dkdkdkdk`. Groups are only written where they are shorter.
+    pub fn compress(self, compress: bool) -> Self {
+        PrintOptions {
+            compress,
+            ..self
+        }
+    }
+
+    /// Sets the dialect to write notation in.
+    pub fn dialect(self, dialect: Dialect) -> Self {
+        PrintOptions {
+            dialect,
+            ..self
+        }
+    }
+}
+
+/// Options for building a notation.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct BuildOptions {
+    simplify: bool,
+    limit: SizeLimit,
+}
+
+impl BuildOptions {
+    /// Sets whether the operators are simplified before they are applied, which avoids
+    /// redundant work such as applying `dual` twice. See [`Notation::simplify`].
+    pub fn simplify(self, simplify: bool) -> Self {
+        BuildOptions {
+            simplify,
+            ..self
+        }
+    }
+
+    /// Sets the size limit, beyond which the notation is not built.
+    pub fn limit(self, limit: SizeLimit) -> Self {
+        BuildOptions {
+            limit,
+            ..self
+        }
+    }
+}
+
+/// The dialects notation may be written in. Parsing accepts either, trying the compact dialect
+/// first.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub enum Dialect {
+    /// Operators and seeds are written as symbols, e.g. `dk5aI`.
+    #[default]
+    Compact,
+    /// Operators and seeds are written as names, separated by whitespace or commas, e.g.
+    /// `dual kis(5) ambo icosahedron`. Integers such as side counts are written in parentheses
+    /// along with any other arguments. A `#` that does not begin a macro starts a comment, which
+    /// runs until the end of the line.
+    Verbose,
+}
+
+/// Writes operators as notation, in the order they are given.
+pub fn print_operators(operators: &[Operator], options: PrintOptions) -> String {
+    let (symbols, separator): (Vec<String>, _) = match options.dialect {
+        Dialect::Compact => (operators.iter().cloned().map(String::from).collect(), ""),
+        Dialect::Verbose => (operators.iter().map(Operator::verbose).collect(), " "),
+    };
+    if options.compress {
+        compress(&symbols, separator)
+    }
+    else {
+        symbols.join(separator)
+    }
+}
+
+/// Writes the symbols of operators, replacing runs of repeated operators with groups. At each
+/// position, the run which shortens the notation the most is chosen, before its own repeated
+/// operators are compressed in turn.
+fn compress(symbols: &[String], separator: &str) -> String {
+    let mut notation = String::new();
+    let mut start = 0;
+    while start < symbols.len() {
+        if start > 0 {
+            notation.push_str(separator);
+        }
+
+        let mut best: Option<(usize, usize)> = None;
+        let mut best_saving = 0;
+        for length in 1..=(symbols.len() - start) / 2 {
+            let block = &symbols[start..start + length];
+            let mut count = 1;
+            while symbols[start + count * length..].starts_with(block) {
+                count += 1;
+            }
+            if count < 2 {
+                continue;
+            }
+
+            let block_length: usize =
+                block.iter().map(|symbol| symbol.len() + separator.len()).sum();
+            let group_length = block_length + count.to_string().len() + "()^".len();
+            if block_length * count > group_length + best_saving {
+                best_saving = block_length * count - group_length;
+                best = Some((length, count));
+            }
+        }
+
+        match best {
+            Some((length, count)) => {
+                let block = compress(&symbols[start..start + length], separator);
+                notation.push_str(&format!("({})^{}", block, count));
+                start += length * count;
+            },
+            None => {
+                notation.push_str(&symbols[start]);
+                start += 1;
+            },
+        }
+    }
+    notation
+}
+
+impl IntoIterator for Notation {
+    type Item = Operator;
+    type IntoIter = std::vec::IntoIter<Operator>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.operators.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Notation {
+    type Item = &'a Operator;
+    type IntoIter = std::slice::Iter<'a, Operator>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.operators.iter()
+    }
+}
+
+/// The most operators a notation may expand to, so that repetitions cannot exhaust memory.
+pub const MAXIMUM_OPERATORS: usize = 1000;
+
+/// The syntaxes notation may be parsed from.
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub enum Syntax {
+    /// The notation of this library, in either dialect.
+    #[default]
+    Native,
+    /// The notation of polyHédronisme, where `n` and `x` are inset and extrude.
+    PolyHedronisme,
+    /// The notation of Antiprism's `conway` program, where `n` is needle, `X` is cross, `K` is
+    /// stake, and `L` and `L0` are lace and joined-lace.
+    Antiprism,
+}
+
+impl std::fmt::Display for Syntax {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Syntax::Native => "native notation",
+            Syntax::PolyHedronisme => "polyHédronisme notation",
+            Syntax::Antiprism => "Antiprism notation",
+        })
+    }
+}
+
+/// Macros that may be used in notation, each naming a sequence of operators. A macro is used by
+/// writing its name after `#`, e.g. `#soft`; the name ends at the first character that is not a
+/// letter, digit or underscore, so a use followed by operators should be grouped, e.g. `(#soft)k`.
+///
+/// Notation may also define macros before its operators, e.g. `#soft := dkdad; (#soft)I`. These
+/// definitions only apply to that notation, and replace any macros of the same name.
+///
+/// Operators defined outside the library may be registered with a symbol, and are then written
+/// with that symbol in either dialect, e.g. `HdI` after registering an operator as `H`.
+///
+/// Notation copied from other programs may be parsed by setting the context's syntax, in which
+/// case macros and registered operators are not available.
+#[derive(Clone, Debug, Default)]
+pub struct NotationContext {
+    macros: FnvHashMap<String, Macro>,
+    operators: FnvHashMap<char, CustomOperator>,
+    syntax: Syntax,
+}
+
+/// The symbols of the built-in operators and seeds, which may not be registered.
+const RESERVED_SYMBOLS: &str = "abcdegijklmnopqrstuwxzEKLTCODIPAY";
+
+#[derive(Clone, Debug)]
+struct Macro {
+    body: String,
+    dialect: Dialect,
+}
+
+impl NotationContext {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Defines a macro, replacing any previous macro of the same name. The body may use other
+    /// macros, which are looked up whenever the macro is used, so they may be defined later.
+    pub fn define(&mut self, name: &str, body: &str) -> Result<(), NotationError> {
+        use pest::Parser;
+
+        NotationParser::parse(Rule::standalone_macro_name, name)
+            .map_err(|error| NotationError::from_pest(error, name))?;
+        let rules = (self.compact_rule(Rule::definition_body), Rule::verbose_definition_body);
+        let (_, dialect) = parse_dialects(rules.0, rules.1, body)?;
+        self.insert(name, body, dialect);
+        Ok(())
+    }
+
+    /// Returns the body of the macro with the given name, if it is defined.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.macros.get(name).map(|definition| definition.body.as_str())
+    }
+
+    fn insert(&mut self, name: &str, body: &str, dialect: Dialect) {
+        let definition = Macro {
+            body: body.into(),
+            dialect,
+        };
+        self.macros.insert(name.into(), definition);
+    }
+
+    /// Registers an operator to be written with the given symbol, replacing any operator
+    /// registered with the same symbol. The symbol must be an ASCII letter not used by a built-in
+    /// operator or seed, which leaves `f`, `h`, `v`, `y`, `B`, `F`, `G`, `H`, `J`, `M`, `N`, `Q`,
+    /// `R`, `S`, `U`, `V`, `W`, `X` and `Z`. Returns an error, registering nothing, for any other
+    /// symbol.
+    pub fn register(
+        &mut self,
+        symbol: char,
+        operator: impl ConwayOperator + 'static,
+    ) -> Result<(), NotationError> {
+        if !symbol.is_ascii_alphabetic() {
+            return Err(NotationError::symbol(symbol, "is not an ASCII letter"));
+        }
+        if RESERVED_SYMBOLS.contains(symbol) {
+            return Err(NotationError::symbol(symbol, "is used by a built-in operator or seed"));
+        }
+        self.operators.insert(symbol, CustomOperator::new(symbol, operator));
+        Ok(())
+    }
+
+    /// Returns the operator registered with the given symbol, if there is one.
+    pub fn registered(&self, symbol: char) -> Option<&CustomOperator> {
+        self.operators.get(&symbol)
+    }
+
+    /// Sets the syntax that notation is parsed from.
+    pub fn set_syntax(&mut self, syntax: Syntax) {
+        self.syntax = syntax;
+    }
+
+    pub fn syntax(&self) -> Syntax {
+        self.syntax
+    }
+
+    /// Parses a sequence of operators, in the order they are written.
+    pub fn parse_operators(&self, value: &str) -> Result<Vec<Operator>, NotationError> {
+        if self.syntax != Syntax::Native {
+            return conway::parse_operators(self.syntax, value);
+        }
+        let compact = self.compact_rule(Rule::expression);
+        let (operators, _) = self.parse(compact, Rule::verbose_expression, value)?;
+        Ok(operators)
+    }
+
+    /// Parses a notation, including its seed.
+    pub fn parse_notation(&self, value: &str) -> Result<Notation, NotationError> {
+        if self.syntax != Syntax::Native {
+            return conway::parse_notation(self.syntax, value);
+        }
+        let compact = self.compact_rule(Rule::notation);
+        let (operators, seed) = self.parse(compact, Rule::verbose_notation, value)?;
+        Ok(Notation::new(seed.unwrap(), operators))
+    }
+
+    /// Returns the rule to parse the compact dialect with, which only accepts registered
+    /// operators once some have been registered.
+    fn compact_rule(&self, rule: Rule) -> Rule {
+        if self.operators.is_empty() {
+            return rule;
+        }
+        match rule {
+            Rule::expression => Rule::registered_expression,
+            Rule::notation => Rule::registered_notation,
+            Rule::definition_body => Rule::registered_definition_body,
+            rule => rule,
+        }
+    }
+
+    fn parse(
+        &self,
+        compact: Rule,
+        verbose: Rule,
+        value: &str,
+    ) -> Result<(Vec<Operator>, Option<Seed>), NotationError> {
+        let (pairs, dialect) = parse_dialects(compact, verbose, value)?;
+        let mut context = self.clone();
+        let mut operators = Vec::new();
+        let mut seed = None;
+        for pair in pairs.flat_map(|pair| pair.into_inner()) {
+            match pair.as_rule() {
+                Rule::definition | Rule::registered_definition | Rule::verbose_definition => {
+                    let mut pairs = pair.into_inner();
+                    let name = pairs.next().unwrap().as_str();
+                    let body = pairs.next().unwrap().as_str();
+                    context.insert(name, body, dialect);
+                },
+                Rule::seed => seed = Some(parse_seed(pair)?),
+                Rule::verbose_seed => seed = Some(parse_verbose_seed(pair)?),
+                Rule::EOI => (),
+                _ => context.expand(pair, &mut Vec::new(), &mut operators)?,
+            }
+        }
+        Ok((operators, seed))
+    }
+
+    /// Parses an operator, group or macro use, appending the operators it expands to. `active`
+    /// holds the macros currently being expanded, so that recursive macros can be detected.
+    fn expand(
+        &self,
+        pair: Pair<Rule>,
+        active: &mut Vec<String>,
+        operators: &mut Vec<Operator>,
+    ) -> Result<(), NotationError> {
+        let span = pair.as_span();
+        match pair.as_rule() {
+            Rule::group | Rule::registered_group | Rule::verbose_group => {
+                let mut group = Vec::new();
+                let mut count = 1;
+                for pair in pair.into_inner() {
+                    if pair.as_rule() == Rule::parameter {
+                        count = parse_integer(pair)? as usize;
+                    }
+                    else {
+                        self.expand(pair, active, &mut group)?;
+                    }
+                }
+                if count == 0 {
+                    let message = "group must be repeated at least once";
+                    return Err(NotationError::invalid(span, message));
+                }
+
+                let total = group.len().saturating_mul(count).saturating_add(operators.len());
+                if total > MAXIMUM_OPERATORS {
+                    return Err(NotationError::invalid(span, "group expands to too many operators"));
+                }
+                for _ in 0..count {
+                    operators.extend(group.iter().cloned());
+                }
+            },
+            Rule::macro_use => {
+                use pest::Parser;
+
+                let name = pair.into_inner().next().unwrap().as_str();
+                let definition = match self.macros.get(name) {
+                    Some(definition) => definition,
+                    None => return Err(NotationError::invalid(span, "macro is not defined")),
+                };
+                if active.iter().any(|active| active == name) {
+                    return Err(NotationError::invalid(span, "macro is defined recursively"));
+                }
+
+                active.push(name.into());
+                let rule = match definition.dialect {
+                    Dialect::Compact => self.compact_rule(Rule::definition_body),
+                    Dialect::Verbose => Rule::verbose_definition_body,
+                };
+                let pairs = NotationParser::parse(rule, &definition.body).unwrap();
+                for pair in pairs.flat_map(|pair| pair.into_inner()) {
+                    for pair in pair.into_inner() {
+                        self.expand(pair, active, operators)
+                            .map_err(|error| NotationError::in_macro(&span, name, error))?;
+                    }
+                }
+                active.pop();
+            },
+            Rule::custom_operator => operators.push(self.parse_custom_operator(pair)?),
+            Rule::verbose_operator => {
+                let name = pair.clone().into_inner().next().unwrap().as_str();
+                let is_registered = |symbol| self.operators.contains_key(&symbol);
+                if name.len() == 1 && name.chars().any(is_registered) {
+                    operators.push(self.parse_custom_operator(pair)?);
+                }
+                else {
+                    operators.push(parse_verbose_operator(pair)?);
+                }
+            },
+            _ => operators.push(parse_operator(pair)?),
+        }
+
+        if operators.len() > MAXIMUM_OPERATORS {
+            return Err(NotationError::invalid(span, "notation has too many operators"));
+        }
+        Ok(())
+    }
+
+    /// Parses an operator written with the symbol it was registered with.
+    fn parse_custom_operator(&self, pair: Pair<Rule>) -> Result<Operator, NotationError> {
+        let span = pair.as_span();
+        let symbol = pair.as_str().chars().next().unwrap();
+        match self.operators.get(&symbol) {
+            Some(custom) if pair.as_str().len() == 1 => Ok(Operator::Custom(custom.clone())),
+            Some(_) => {
+                Err(NotationError::invalid(span, "registered operators do not accept arguments"))
+            },
+            None => Err(NotationError::invalid(span, "operator is not registered")),
+        }
+    }
+}
+
+/// Parses the value in the compact dialect, or in the verbose dialect if it is not compact. If
+/// neither dialect matches, the error from the one that matched more of the value is returned.
+fn parse_dialects(
+    compact: Rule,
+    verbose: Rule,
+    value: &str,
+) -> Result<(Pairs<'_, Rule>, Dialect), NotationError> {
+    use pest::Parser;
+
+    let compact_error = match NotationParser::parse(compact, value) {
+        Ok(pairs) => return Ok((pairs, Dialect::Compact)),
+        Err(error) => NotationError::from_pest(error, value),
+    };
+    match NotationParser::parse(verbose, value) {
+        Ok(pairs) => Ok((pairs, Dialect::Verbose)),
+        Err(error) => {
+            let verbose_error = NotationError::from_pest(error, value);
+            if verbose_error.span.start > compact_error.span.start {
+                Err(verbose_error)
+            }
+            else {
+                Err(compact_error)
+            }
+        },
+    }
+}
+
+fn parse_seed(seed_pair: Pair<Rule>) -> Result<Seed, NotationError> {
+    let pair = seed_pair.into_inner().next().unwrap();
+    let span = pair.as_span();
+    let rule = pair.as_rule();
+    if rule == Rule::platonic {
+        let platonic = Platonic::all()
+            .iter()
+            .copied()
+            .find(|platonic| <&str>::from(*platonic) == pair.as_str())
+            .unwrap();
+        return Ok(Seed::Platonic(platonic));
+    }
+
+    let sides = parse_integer(pair.into_inner().next().unwrap())?;
+    if sides < 3 {
+        return Err(NotationError::invalid(span, "seed must have at least 3 sides"));
+    }
+    Ok(match rule {
+        Rule::prism => Seed::Prism(sides),
+        Rule::antiprism => Seed::Antiprism(sides),
+        Rule::pyramid => Seed::Pyramid(sides),
+        _ => unreachable!(),
+    })
+}
+
+fn parse_verbose_seed(seed_pair: Pair<Rule>) -> Result<Seed, NotationError> {
+    let span = seed_pair.as_span();
+    let mut pairs = seed_pair.into_inner();
+    let name = pairs.next().unwrap().as_str();
+    let sides = match pairs.next() {
+        Some(pair) => Some(parse_integer(pair)?),
+        None => None,
+    };
+
+    let platonic = Platonic::all()
+        .iter()
+        .copied()
+        .find(|platonic| verbose_seed(Seed::Platonic(*platonic)) == name);
+    match (platonic, sides) {
+        (Some(platonic), None) => return Ok(Seed::Platonic(platonic)),
+        (Some(_), Some(_)) => {
+            return Err(NotationError::invalid(span, "Platonic seed does not accept a side count"));
+        },
+        (None, None) => return Err(NotationError::invalid(span, "seed requires a side count")),
+        (None, Some(sides)) if sides < 3 => {
+            return Err(NotationError::invalid(span, "seed must have at least 3 sides"));
+        },
+        _ => (),
+    }
+
+    let sides = sides.unwrap();
+    Ok(match name {
+        "prism" => Seed::Prism(sides),
+        "antiprism" => Seed::Antiprism(sides),
+        "pyramid" => Seed::Pyramid(sides),
+        _ => unreachable!(),
+    })
+}
+
+/// Writes a seed in the compact dialect, e.g. `I` or `P5`.
+fn compact_seed(seed: Seed) -> String {
+    match seed {
+        Seed::Platonic(_) => <&str>::from(seed).into(),
+        Seed::Prism(sides) | Seed::Antiprism(sides) | Seed::Pyramid(sides) => {
+            format!("{}{}", <&str>::from(seed), sides)
+        },
+    }
+}
+
+/// Writes a seed in the verbose dialect, e.g. `icosahedron` or `prism(5)`.
+fn verbose_seed(seed: Seed) -> String {
+    match seed {
+        Seed::Platonic(platonic) => platonic.to_string().to_lowercase(),
+        Seed::Prism(sides) => format!("prism({})", sides),
+        Seed::Antiprism(sides) => format!("antiprism({})", sides),
+        Seed::Pyramid(sides) => format!("pyramid({})", sides),
+    }
+}
+
+/// The reason Conway notation could not be parsed.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NotationError {
+    span: std::ops::Range<usize>,
+    character: Option<char>,
+    expected: Vec<Rule>,
+    message: String,
+}
+
+impl NotationError {
+    /// Creates an error for text that parsed, but whose value is out of range.
+    pub(super) fn invalid(span: pest::Span, message: &str) -> Self {
+        NotationError {
+            span: span.start()..span.end(),
+            character: span.as_str().chars().next(),
+            expected: Vec::new(),
+            message: format!("{} at position {}", message, span.start()),
+        }
+    }
+
+    /// Creates an error for the use of a macro, from an error within its body.
+    fn in_macro(span: &pest::Span, name: &str, error: NotationError) -> Self {
+        NotationError {
+            span: span.start()..span.end(),
+            character: span.as_str().chars().next(),
+            expected: Vec::new(),
+            message: format!("{}, in macro `#{}` used at position {}", error, name, span.start()),
+        }
+    }
+
+    /// Creates an error for a symbol which operators may not be registered with.
+    fn symbol(symbol: char, message: &str) -> Self {
+        NotationError {
+            span: 0..symbol.len_utf8(),
+            character: Some(symbol),
+            expected: Vec::new(),
+            message: format!("symbol `{}` {}", symbol, message),
+        }
+    }
+
+    pub(super) fn from_pest(error: pest::error::Error<Rule>, notation: &str) -> Self {
+        use pest::error::{ErrorVariant, InputLocation};
+
+        let start = match error.location {
+            InputLocation::Pos(position) => position,
+            InputLocation::Span((start, _)) => start,
+        };
+        let character = notation[start..].chars().next();
+        let end = match error.location {
+            InputLocation::Pos(_) => start + character.map_or(0, char::len_utf8),
+            InputLocation::Span((_, end)) => end,
+        };
+        let mut expected: Vec<Rule> = Vec::new();
+        if let ErrorVariant::ParsingError { positives, .. } = error.variant {
+            for rule in positives {
+                if !expected.contains(&rule) {
+                    expected.push(rule);
+                }
+            }
+        }
+
+        let mut message = match character {
+            Some(character) => format!("unexpected `{}` at position {}", character, start),
+            None => format!("unexpected end of notation at position {}", start),
+        };
+        if !expected.is_empty() {
+            let names: Vec<_> = expected.iter().map(|rule| describe(*rule)).collect();
+            message.push_str(", expected ");
+            message.push_str(&names.join(", "));
+        }
+
+        NotationError {
+            span: start..end,
+            character,
+            expected,
+            message,
+        }
+    }
+
+    /// The byte range of the notation the error refers to.
+    pub fn span(&self) -> std::ops::Range<usize> {
+        self.span.clone()
+    }
+
+    /// The first character of the span, or `None` if the notation ended unexpectedly.
+    pub fn character(&self) -> Option<char> {
+        self.character
+    }
+
+    /// The operators and parameters that could have appeared at the start of the span. This is
+    /// empty if the notation is well formed, but a value is out of range.
+    pub fn expected(&self) -> &[Rule] {
+        &self.expected
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::fmt::Display for NotationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+/// Describes a rule of the grammar the way it is written in notation.
+fn describe(rule: Rule) -> &'static str {
+    match rule {
+        Rule::operator => "an operator",
+        Rule::ambo => "ambo `a`",
+        Rule::dual => "dual `d`",
+        Rule::kis => "kis `k`",
+        Rule::truncate => "truncate `t`",
+        Rule::join => "join `j`",
+        Rule::ortho => "ortho `o`",
+        Rule::expand => "expand `e`",
+        Rule::gyro => "gyro `g`",
+        Rule::snub => "snub `s`",
+        Rule::bevel => "bevel `b`",
+        Rule::meta => "meta `m`",
+        Rule::needle => "needle `n`",
+        Rule::zip => "zip `z`",
+        Rule::propeller => "propeller `p`",
+        Rule::reflect => "reflect `r`",
+        Rule::chamfer => "chamfer `c`",
+        Rule::whirl => "whirl `w`",
+        Rule::loft => "loft `l`",
+        Rule::joined_lace => "joined-lace `L0`",
+        Rule::lace => "lace `L`",
+        Rule::stake => "stake `K`",
+        Rule::quinto => "quinto `q`",
+        Rule::cross => "cross `x`",
+        Rule::subdivide => "subdivide `u`",
+        Rule::goldberg_coxeter => "Goldberg-Coxeter `c(m,n)`",
+        Rule::inset => "inset `i`",
+        Rule::extrude => "extrude `E`",
+        Rule::custom_operator => "a registered operator",
+        Rule::seed => "a seed such as `I` or `P5`",
+        Rule::platonic => "a Platonic seed `T`, `C`, `O`, `D` or `I`",
+        Rule::prism => "prism `P`",
+        Rule::antiprism => "antiprism `A`",
+        Rule::pyramid => "pyramid `Y`",
+        Rule::group | Rule::registered_group => "a group `(...)`",
+        Rule::item | Rule::registered_item => "an operator or group",
+        Rule::macro_use => "a macro `#name`",
+        Rule::macro_name | Rule::standalone_macro_name => "a macro name",
+        Rule::definition | Rule::registered_definition => "a macro definition `#name := ...;`",
+        Rule::body
+        | Rule::definition_body
+        | Rule::registered_body
+        | Rule::registered_definition_body => "operators",
+        Rule::selector => "a face selector",
+        Rule::criterion => "a criterion",
+        Rule::sides_criterion => "`sides=`",
+        Rule::class_criterion => "`class=`",
+        Rule::area_criterion => "`area=`",
+        Rule::normal_criterion => "`normal=`",
+        Rule::index_criterion => "`index=`",
+        Rule::seed_criterion => "`seed=`",
+        Rule::arguments => "arguments `(...)`",
+        Rule::argument => "an argument",
+        Rule::argument_name => "a parameter name",
+        Rule::edge_selector => "an edge selector",
+        Rule::parameter => "an integer",
+        Rule::minimum | Rule::maximum | Rule::bound | Rule::decimal => "a number",
+        Rule::signed_decimal => "a signed number",
+        Rule::left_handed => "`'`",
+        Rule::verbose_operator | Rule::operator_name | Rule::verbose_item => "an operator name",
+        Rule::verbose_arguments => "arguments `(...)`",
+        Rule::verbose_argument => "an argument",
+        Rule::verbose_group => "a group `(...)`",
+        Rule::verbose_seed | Rule::seed_name => "a seed such as `icosahedron` or `prism(5)`",
+        Rule::verbose_definition => "a macro definition `#name := ...;`",
+        Rule::verbose_body | Rule::verbose_definition_body => "operators",
+        Rule::separator | Rule::space => "whitespace",
+        Rule::comment => "a comment",
+        Rule::conway_term | Rule::conway_symbol => "an operator or seed",
+        Rule::conway_arguments => "parameters `(...)`",
+        Rule::expression
+        | Rule::notation
+        | Rule::registered_expression
+        | Rule::registered_notation
+        | Rule::verbose_expression
+        | Rule::verbose_notation
+        | Rule::conway
+        | Rule::EOI => "the end of the notation",
+    }
+}
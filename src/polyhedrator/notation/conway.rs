@@ -0,0 +1,213 @@
+//! Parses the notation of other programs, whose symbols mostly match ours, but differ for some
+//! operators and parameters.
+
+use super::{Notation, NotationError, NotationParser, Rule, Syntax};
+use crate::polyhedrator::operators::{
+    parse_integer, Chamfer, Chirality, Cross, Extrude, GoldbergCoxeter, Inset, JoinedLace, Kis,
+    Lace, Loft, Needle, Operator, Quinto, Stake, Truncate, Whirl, Zip,
+};
+use crate::polyhedrator::seeds::{Platonic, Seed};
+use pest::iterators::Pair;
+use pest::Span;
+
+/// What a symbol of another program means here.
+enum Meaning {
+    /// An operator which does not take a parameter.
+    Fixed(Operator),
+    /// An operator taking a side count or vertex degree, which is zero if absent.
+    Counted(fn(u32) -> Operator),
+    /// An operator with no counterpart here, given by name.
+    Unsupported(&'static str),
+}
+
+/// Parses a sequence of operators written in the given syntax.
+pub(super) fn parse_operators(
+    syntax: Syntax,
+    value: &str,
+) -> Result<Vec<Operator>, NotationError> {
+    let terms = parse_terms(value)?;
+    terms.into_iter().map(|term| parse_operator(syntax, term)).collect()
+}
+
+/// Parses a notation written in the given syntax, where the last symbol is the seed.
+pub(super) fn parse_notation(syntax: Syntax, value: &str) -> Result<Notation, NotationError> {
+    let mut terms = parse_terms(value)?;
+    let seed = match terms.pop() {
+        Some(term) => parse_seed(syntax, term)?,
+        None => {
+            let end = Span::new(value, value.len(), value.len()).unwrap();
+            return Err(NotationError::invalid(end, "notation requires a seed"));
+        },
+    };
+    let operators = terms.into_iter().map(|term| parse_operator(syntax, term));
+    Ok(Notation::new(seed, operators.collect::<Result<_, _>>()?))
+}
+
+fn parse_terms(value: &str) -> Result<Vec<Pair<'_, Rule>>, NotationError> {
+    use pest::Parser;
+
+    let pairs = NotationParser::parse(Rule::conway, value)
+        .map_err(|error| NotationError::from_pest(error, value))?;
+    let terms = pairs.flat_map(|pair| pair.into_inner());
+    Ok(terms.filter(|pair| pair.as_rule() == Rule::conway_term).collect())
+}
+
+fn parse_operator(syntax: Syntax, term: Pair<Rule>) -> Result<Operator, NotationError> {
+    let span = term.as_span();
+    let mut pairs = term.into_inner();
+    let symbol = pairs.next().unwrap().as_str();
+    let count = parse_count(syntax, pairs.next())?;
+
+    match (meaning(syntax, symbol), count) {
+        (Some(Meaning::Fixed(operator)), None) => Ok(operator),
+        (Some(Meaning::Fixed(_)), Some(_)) => {
+            Err(NotationError::invalid(span, "operator does not accept integers"))
+        },
+        (Some(Meaning::Counted(operator)), count) => Ok(operator(count.unwrap_or(0))),
+        (Some(Meaning::Unsupported(name)), _) => {
+            let message = format!("{} (`{}`) from {} is not supported", name, symbol, syntax);
+            Err(NotationError::invalid(span, &message))
+        },
+        (None, _) => {
+            let message = format!("`{}` is not an operator in {}", symbol, syntax);
+            Err(NotationError::invalid(span, &message))
+        },
+    }
+}
+
+fn parse_seed(syntax: Syntax, term: Pair<Rule>) -> Result<Seed, NotationError> {
+    let span = term.as_span();
+    let mut pairs = term.into_inner();
+    let symbol = pairs.next().unwrap().as_str();
+    let count = parse_count(syntax, pairs.next())?;
+
+    let platonic = match symbol {
+        "T" => Some(Platonic::Tetrahedron),
+        "C" => Some(Platonic::Cube),
+        "O" => Some(Platonic::Octahedron),
+        "D" => Some(Platonic::Dodecahedron),
+        "I" => Some(Platonic::Icosahedron),
+        _ => None,
+    };
+    if let Some(platonic) = platonic {
+        return match count {
+            Some(_) => {
+                let message = "Platonic seed does not accept a side count";
+                Err(NotationError::invalid(span, message))
+            },
+            None => Ok(Seed::Platonic(platonic)),
+        };
+    }
+
+    let seed: fn(u32) -> Seed = match symbol {
+        "P" => Seed::Prism,
+        "A" => Seed::Antiprism,
+        "Y" => Seed::Pyramid,
+        _ => {
+            let message = match symbol {
+                "J" => format!("Johnson solid (`J`) seeds from {} are not supported", syntax),
+                "U" => format!("cupola (`U`) seeds from {} are not supported", syntax),
+                "V" => format!("anticupola (`V`) seeds from {} are not supported", syntax),
+                _ => format!("`{}` is not a seed in {}", symbol, syntax),
+            };
+            return Err(NotationError::invalid(span, &message));
+        },
+    };
+    match count {
+        None => Err(NotationError::invalid(span, "seed requires a side count")),
+        Some(sides) if sides < 3 => {
+            Err(NotationError::invalid(span, "seed must have at least 3 sides"))
+        },
+        Some(sides) => Ok(seed(sides)),
+    }
+}
+
+/// Parses the integer following a symbol, which may be written in parentheses. Decimal
+/// parameters are rejected, since their meaning differs between programs.
+fn parse_count(syntax: Syntax, pair: Option<Pair<Rule>>) -> Result<Option<u32>, NotationError> {
+    let pair = match pair {
+        Some(pair) => pair,
+        None => return Ok(None),
+    };
+    if pair.as_rule() == Rule::parameter {
+        return parse_integer(pair).map(Some);
+    }
+
+    let mut arguments = pair.into_inner();
+    let first = arguments.next().unwrap();
+    if let Some(second) = arguments.next() {
+        let message = format!("decimal parameters from {} are not supported", syntax);
+        return Err(NotationError::invalid(second.as_span(), &message));
+    }
+    match first.as_str().parse::<u32>() {
+        Ok(count) => Ok(Some(count)),
+        Err(_) => {
+            let message = format!("decimal parameters from {} are not supported", syntax);
+            Err(NotationError::invalid(first.as_span(), &message))
+        },
+    }
+}
+
+/// Returns what an operator symbol means in the given syntax, if it is an operator at all.
+fn meaning(syntax: Syntax, symbol: &str) -> Option<Meaning> {
+    use Meaning::*;
+
+    let meaning = match (syntax, symbol) {
+        (_, "a") => Fixed(Operator::Ambo),
+        (_, "b") => Fixed(Operator::Bevel),
+        (_, "c") => Fixed(Operator::Chamfer(Chamfer::default())),
+        (_, "d") => Fixed(Operator::Dual),
+        (_, "e") => Fixed(Operator::Expand),
+        (_, "g") => Fixed(Operator::Gyro(Chirality::Right)),
+        (_, "j") => Fixed(Operator::Join),
+        (_, "k") => {
+            Counted(|sides| Operator::Kis(Kis::restrict_to_sides_and_scale_apex(sides, 0.0)))
+        },
+        (_, "l") => Counted(|sides| Operator::Loft(Loft::restrict_to_sides(sides))),
+        (_, "m") => Fixed(Operator::Meta),
+        (_, "o") => Fixed(Operator::Ortho),
+        (_, "p") => Fixed(Operator::Propeller(Chirality::Right)),
+        (_, "q") => Counted(|sides| Operator::Quinto(Quinto::restrict_to_sides(sides))),
+        (_, "r") => Fixed(Operator::Reflect),
+        (_, "s") => Fixed(Operator::Snub(Chirality::Right)),
+        (_, "t") => Counted(|degree| Operator::Truncate(Truncate::restrict_to_degree(degree))),
+        (_, "u") => Counted(|frequency| match frequency {
+            0 => Operator::GoldbergCoxeter(GoldbergCoxeter::default()),
+            frequency => Operator::GoldbergCoxeter(GoldbergCoxeter::subdivide(frequency)),
+        }),
+        (_, "w") => Fixed(Operator::Whirl(Whirl::default())),
+        (_, "z") => Counted(|sides| Operator::Zip(Zip::restrict_to_sides(sides))),
+
+        (Syntax::PolyHedronisme, "n") => {
+            Counted(|sides| Operator::Inset(Inset::restrict_to_sides(sides)))
+        },
+        (Syntax::PolyHedronisme, "x") => {
+            Counted(|sides| Operator::Extrude(Extrude::restrict_to_sides(sides)))
+        },
+        (Syntax::PolyHedronisme, "A") => Unsupported("adjust"),
+        (Syntax::PolyHedronisme, "C") => Unsupported("canonicalize"),
+        (Syntax::PolyHedronisme, "H") => Unsupported("hollow"),
+        (Syntax::PolyHedronisme, "P") => Unsupported("perspectiva"),
+        (Syntax::PolyHedronisme, "Z") => Unsupported("triangulate"),
+
+        (Syntax::Antiprism, "n") => {
+            Counted(|sides| Operator::Needle(Needle::restrict_to_sides(sides)))
+        },
+        (Syntax::Antiprism, "K") => {
+            Counted(|sides| Operator::Stake(Stake::restrict_to_sides(sides)))
+        },
+        (Syntax::Antiprism, "L") => {
+            Counted(|sides| Operator::Lace(Lace::restrict_to_sides(sides)))
+        },
+        (Syntax::Antiprism, "L0") => {
+            Counted(|sides| Operator::JoinedLace(JoinedLace::restrict_to_sides(sides)))
+        },
+        (Syntax::Antiprism, "X") => {
+            Counted(|sides| Operator::Cross(Cross::restrict_to_sides(sides)))
+        },
+        (Syntax::Antiprism, "M") => Unsupported("medial"),
+        (Syntax::Antiprism, "M0") => Unsupported("joined-medial"),
+        _ => return None,
+    };
+    Some(meaning)
+}
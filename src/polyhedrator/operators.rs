@@ -1,11 +1,43 @@
+use super::notation::{NotationContext, NotationError, Rule};
+use super::CustomOperator;
+use cgmath::{InnerSpace, Vector3};
+use pest::iterators::{Pair, Pairs};
+use pest::Span;
+use std::hash::{Hash, Hasher};
+
 /// A Conway operator to apply to a polyhedron.
 /// See [https://en.wikipedia.org/wiki/Conway_polyhedron_notation](Conway polyhedron notation) for
 /// more information.
-#[derive(Copy, Clone, PartialOrd, PartialEq, Debug)]
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash, Debug)]
 pub enum Operator {
     Ambo,
     Dual,
     Kis(Kis),
+    Truncate(Truncate),
+    Join,
+    Ortho,
+    Expand,
+    Gyro(Chirality),
+    Snub(Chirality),
+    Bevel,
+    Meta,
+    Needle(Needle),
+    Zip(Zip),
+    Propeller(Chirality),
+    Reflect,
+    Chamfer(Chamfer),
+    Whirl(Whirl),
+    Loft(Loft),
+    Lace(Lace),
+    Stake(Stake),
+    Quinto(Quinto),
+    JoinedLace(JoinedLace),
+    Cross(Cross),
+    GoldbergCoxeter(GoldbergCoxeter),
+    Inset(Inset),
+    Extrude(Extrude),
+    /// An operator defined outside the library, written with the symbol it was registered with.
+    Custom(CustomOperator),
 }
 
 impl From<Operator> for String {
@@ -14,23 +46,529 @@ impl From<Operator> for String {
             Operator::Ambo => "a".into(),
             Operator::Dual => "d".into(),
             Operator::Kis(kis) => {
-                if kis.side_count == 0 {
-                    "k".into()
+                // Parsed kis operators default to flat pyramids, rather than `Kis::default()`.
+                let symbol = selected("k", &kis.selector);
+                if kis.apex_scale != 0.0 {
+                    format!("{}({})", symbol, kis.apex_scale)
+                }
+                else {
+                    symbol
+                }
+            },
+            Operator::Truncate(truncate) => {
+                let symbol = restricted("t", truncate.degree());
+                if truncate.depth != Truncate::default().depth {
+                    format!("{}({})", symbol, truncate.depth)
+                }
+                else {
+                    symbol
+                }
+            },
+            Operator::Join => "j".into(),
+            Operator::Ortho => "o".into(),
+            Operator::Expand => "e".into(),
+            Operator::Gyro(chirality) => format!("g{}", chirality.suffix()),
+            Operator::Snub(chirality) => format!("s{}", chirality.suffix()),
+            Operator::Bevel => "b".into(),
+            Operator::Meta => "m".into(),
+            Operator::Needle(needle) => restricted("n", needle.side_count()),
+            Operator::Zip(zip) => selected("z", &zip.selector),
+            Operator::Propeller(chirality) => format!("p{}", chirality.suffix()),
+            Operator::Reflect => "r".into(),
+            Operator::Chamfer(chamfer) => {
+                let mut notation = String::from("c");
+                if chamfer.selector != EdgeSelector::default() {
+                    let (first, second) = chamfer.selector.side_counts;
+                    notation.push_str(&format!("{{{},{}}}", first, second));
+                }
+                if chamfer.ratio != Chamfer::default().ratio {
+                    notation.push_str(&format!("({})", chamfer.ratio));
+                }
+                notation
+            },
+            Operator::Whirl(whirl) => {
+                if whirl.ratio == Whirl::default().ratio {
+                    format!("w{}", whirl.chirality.suffix())
+                }
+                else {
+                    format!("w{}({})", whirl.chirality.suffix(), whirl.ratio)
+                }
+            },
+            Operator::Loft(loft) => selected("l", &loft.selector),
+            Operator::Lace(lace) => selected("L", &lace.selector),
+            Operator::Stake(stake) => selected("K", &stake.selector),
+            Operator::Quinto(quinto) => selected("q", &quinto.selector),
+            Operator::JoinedLace(joined_lace) => selected("L0", &joined_lace.selector),
+            Operator::Cross(cross) => selected("x", &cross.selector),
+            Operator::GoldbergCoxeter(goldberg_coxeter) => {
+                let GoldbergCoxeter { m, n } = goldberg_coxeter;
+                if goldberg_coxeter == GoldbergCoxeter::default() {
+                    "u".into()
+                }
+                else if n == 0 {
+                    format!("u{}", m)
+                }
+                else {
+                    format!("c({},{})", m, n)
+                }
+            },
+            Operator::Inset(inset) => {
+                let symbol = selected("i", &inset.selector);
+                let default = Inset::default();
+                if inset.distance != default.distance {
+                    format!("{}({},{})", symbol, inset.ratio, inset.distance)
+                }
+                else if inset.ratio != default.ratio {
+                    format!("{}({})", symbol, inset.ratio)
+                }
+                else {
+                    symbol
+                }
+            },
+            Operator::Extrude(extrude) => {
+                let symbol = selected("E", &extrude.selector);
+                let default = Extrude::default();
+                if extrude.ratio != default.ratio {
+                    format!("{}({},{})", symbol, extrude.distance, extrude.ratio)
+                }
+                else if extrude.distance != default.distance {
+                    format!("{}({})", symbol, extrude.distance)
+                }
+                else {
+                    symbol
+                }
+            },
+            Operator::Custom(custom) => custom.symbol().to_string(),
+        }
+    }
+}
+
+/// Formats an operator symbol followed by the faces it selects. Selectors that only restrict the
+/// side count are written as a count, like any other restriction.
+fn selected(symbol: &str, selector: &FaceSelector) -> String {
+    if selector.is_side_count_only() {
+        restricted(symbol, selector.side_count)
+    }
+    else {
+        format!("{}{}", symbol, selector)
+    }
+}
+
+/// Formats an operator symbol followed by its side count, if it has one.
+fn restricted(symbol: &str, count: u32) -> String {
+    if count == 0 {
+        symbol.into()
+    }
+    else {
+        format!("{}{}", symbol, count)
+    }
+}
+
+impl Operator {
+    /// Writes the operator in the verbose dialect, e.g. `kis(5, h=0.2)`.
+    pub fn verbose(&self) -> String {
+        match self {
+            Operator::Ambo => "ambo".into(),
+            Operator::Dual => "dual".into(),
+            Operator::Kis(kis) => {
+                let mut arguments = Vec::new();
+                if kis.apex_scale != 0.0 {
+                    arguments.push(format!("h={}", kis.apex_scale));
+                }
+                verbose_selected("kis", &kis.selector, arguments)
+            },
+            Operator::Truncate(truncate) => {
+                let mut arguments = counted(truncate.degree());
+                if truncate.depth != Truncate::default().depth {
+                    arguments.push(format!("d={}", truncate.depth));
+                }
+                verbose("truncate", arguments)
+            },
+            Operator::Join => "join".into(),
+            Operator::Ortho => "ortho".into(),
+            Operator::Expand => "expand".into(),
+            Operator::Gyro(chirality) => format!("gyro{}", chirality.suffix()),
+            Operator::Snub(chirality) => format!("snub{}", chirality.suffix()),
+            Operator::Bevel => "bevel".into(),
+            Operator::Meta => "meta".into(),
+            Operator::Needle(needle) => verbose("needle", counted(needle.side_count())),
+            Operator::Zip(zip) => verbose_selected("zip", &zip.selector, Vec::new()),
+            Operator::Propeller(chirality) => format!("propeller{}", chirality.suffix()),
+            Operator::Reflect => "reflect".into(),
+            Operator::Chamfer(chamfer) => {
+                let mut name = String::from("chamfer");
+                if chamfer.selector != EdgeSelector::default() {
+                    let (first, second) = chamfer.selector.side_counts;
+                    name.push_str(&format!("{{{},{}}}", first, second));
+                }
+                let mut arguments = Vec::new();
+                if chamfer.ratio != Chamfer::default().ratio {
+                    arguments.push(format!("r={}", chamfer.ratio));
+                }
+                verbose(&name, arguments)
+            },
+            Operator::Whirl(whirl) => {
+                let mut arguments = Vec::new();
+                if whirl.ratio != Whirl::default().ratio {
+                    arguments.push(format!("r={}", whirl.ratio));
+                }
+                verbose(&format!("whirl{}", whirl.chirality.suffix()), arguments)
+            },
+            Operator::Loft(loft) => verbose_selected("loft", &loft.selector, Vec::new()),
+            Operator::Lace(lace) => verbose_selected("lace", &lace.selector, Vec::new()),
+            Operator::Stake(stake) => verbose_selected("stake", &stake.selector, Vec::new()),
+            Operator::Quinto(quinto) => verbose_selected("quinto", &quinto.selector, Vec::new()),
+            Operator::JoinedLace(joined_lace) => {
+                verbose_selected("joined-lace", &joined_lace.selector, Vec::new())
+            },
+            Operator::Cross(cross) => verbose_selected("cross", &cross.selector, Vec::new()),
+            Operator::GoldbergCoxeter(goldberg_coxeter) => {
+                let GoldbergCoxeter { m, n } = *goldberg_coxeter;
+                if *goldberg_coxeter == GoldbergCoxeter::default() {
+                    "subdivide".into()
+                }
+                else if n == 0 {
+                    format!("subdivide({})", m)
                 }
                 else {
-                    format!("k{}", kis.side_count)
+                    format!("goldberg-coxeter({}, {})", m, n)
+                }
+            },
+            Operator::Inset(inset) => {
+                let default = Inset::default();
+                let mut arguments = Vec::new();
+                if inset.ratio != default.ratio {
+                    arguments.push(format!("r={}", inset.ratio));
+                }
+                if inset.distance != default.distance {
+                    arguments.push(format!("d={}", inset.distance));
                 }
+                verbose_selected("inset", &inset.selector, arguments)
             },
+            Operator::Extrude(extrude) => {
+                let default = Extrude::default();
+                let mut arguments = Vec::new();
+                if extrude.distance != default.distance {
+                    arguments.push(format!("d={}", extrude.distance));
+                }
+                if extrude.ratio != default.ratio {
+                    arguments.push(format!("r={}", extrude.ratio));
+                }
+                verbose_selected("extrude", &extrude.selector, arguments)
+            },
+            Operator::Custom(custom) => custom.symbol().to_string(),
+        }
+    }
+}
+
+/// Formats the verbose name of an operator followed by its arguments in parentheses, if any.
+fn verbose(name: &str, arguments: Vec<String>) -> String {
+    if arguments.is_empty() {
+        name.into()
+    }
+    else {
+        format!("{}({})", name, arguments.join(", "))
+    }
+}
+
+/// Formats the verbose name of an operator that selects faces, preceding its other arguments with
+/// its side count, or following its name with its selector.
+fn verbose_selected(name: &str, selector: &FaceSelector, arguments: Vec<String>) -> String {
+    if selector.is_side_count_only() {
+        let mut counts = counted(selector.side_count);
+        counts.extend(arguments);
+        verbose(name, counts)
+    }
+    else {
+        verbose(&format!("{}{}", name, selector), arguments)
+    }
+}
+
+/// Returns the count as an argument, unless it is zero.
+fn counted(count: u32) -> Vec<String> {
+    if count == 0 {
+        Vec::new()
+    }
+    else {
+        vec![count.to_string()]
+    }
+}
+
+/// The handedness of a chiral operator or polyhedron. Left-handed operators produce the mirror
+/// image of their right-handed counterparts.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+pub enum Chirality {
+    Left,
+    Right,
+}
+
+impl Chirality {
+    /// Returns the opposite handedness.
+    pub fn reversed(self) -> Chirality {
+        match self {
+            Chirality::Left => Chirality::Right,
+            Chirality::Right => Chirality::Left,
+        }
+    }
+
+    fn suffix(self) -> &'static str {
+        match self {
+            Chirality::Left => "'",
+            Chirality::Right => "",
+        }
+    }
+}
+
+/// Selects the faces an operator acts upon. Every face is selected by default, and each added
+/// criterion narrows the selection further, so a face is only selected if it meets all of them.
+#[derive(Clone, PartialOrd, PartialEq, Debug, Default)]
+pub struct FaceSelector {
+    side_count: u32,
+    class: Option<u32>,
+    minimum_area: Option<f64>,
+    maximum_area: Option<f64>,
+    normal: Option<([f64; 3], f64)>,
+    indices: Option<Vec<u32>>,
+    seed_faces: Option<Vec<u32>>,
+}
+
+impl FaceSelector {
+    /// Only selects faces with the given number of sides.
+    /// If `side_count` is zero, faces with any number of sides are selected.
+    pub fn with_sides(self, side_count: u32) -> Self {
+        FaceSelector { side_count, ..self }
+    }
+
+    /// Only selects faces of the given class, as numbered by `Polyhedron::classify_faces`.
+    pub fn with_class(self, class: u32) -> Self {
+        FaceSelector {
+            class: Some(class),
+            ..self
+        }
+    }
+
+    /// Only selects faces with an area between the given bounds, inclusive. A missing bound
+    /// leaves that side of the range open.
+    /// # Restrictions
+    /// Panics if either bound is negative, NaN or infinite, or if the minimum exceeds the maximum.
+    pub fn with_area(self, minimum: Option<f64>, maximum: Option<f64>) -> Self {
+        for bound in minimum.iter().chain(maximum.iter()) {
+            assert!(bound.is_finite(), "Area bounds must be finite.");
+            assert!(*bound >= 0.0, "Area bounds must not be negative.");
+        }
+        if let (Some(minimum), Some(maximum)) = (minimum, maximum) {
+            assert!(minimum <= maximum, "Minimum area must not exceed the maximum area.");
+        }
+        FaceSelector {
+            minimum_area: minimum,
+            maximum_area: maximum,
+            ..self
+        }
+    }
+
+    /// Only selects faces whose normal is within `angle` degrees of `direction`.
+    /// # Restrictions
+    /// Panics if `direction` is zero or not finite, or if `angle` is not between zero and 180,
+    /// inclusive.
+    pub fn with_normal(self, direction: Vector3<f64>, angle: f64) -> Self {
+        let length = direction.magnitude();
+        assert!(length.is_finite() && length > 0.0, "Direction must be finite and non-zero.");
+        assert!((0.0..=180.0).contains(&angle), "Angle must be between 0 and 180 degrees.");
+        FaceSelector {
+            normal: Some((direction.into(), angle)),
+            ..self
+        }
+    }
+
+    /// Only selects the faces with the given indices.
+    pub fn with_indices(self, indices: impl IntoIterator<Item = u32>) -> Self {
+        FaceSelector {
+            indices: Some(sorted(indices)),
+            ..self
+        }
+    }
+
+    /// Only selects faces descended from the seed faces with the given indices. Faces created
+    /// from the vertices or edges of a polyhedron, such as those of its dual, have no seed face
+    /// and are never selected.
+    pub fn with_seed_faces(self, seed_faces: impl IntoIterator<Item = u32>) -> Self {
+        FaceSelector {
+            seed_faces: Some(sorted(seed_faces)),
+            ..self
+        }
+    }
+
+    /// The number of sides a face must have to be selected, or zero if any number is allowed.
+    pub fn side_count(&self) -> u32 {
+        self.side_count
+    }
+
+    pub fn class(&self) -> Option<u32> {
+        self.class
+    }
+
+    /// Returns the minimum and maximum area a face may have to be selected.
+    pub fn area(&self) -> (Option<f64>, Option<f64>) {
+        (self.minimum_area, self.maximum_area)
+    }
+
+    /// Returns the direction a face's normal must be near to be selected, along with the largest
+    /// angle between them, in degrees.
+    pub fn normal(&self) -> Option<(Vector3<f64>, f64)> {
+        self.normal.map(|(direction, angle)| (direction.into(), angle))
+    }
+
+    /// Returns the indices of the faces that may be selected, in ascending order.
+    pub fn indices(&self) -> Option<&[u32]> {
+        self.indices.as_deref()
+    }
+
+    /// Returns the indices of the seed faces a face must descend from to be selected, in
+    /// ascending order.
+    pub fn seed_faces(&self) -> Option<&[u32]> {
+        self.seed_faces.as_deref()
+    }
+
+    fn is_side_count_only(&self) -> bool {
+        *self == FaceSelector::default().with_sides(self.side_count)
+    }
+}
+
+impl std::fmt::Display for FaceSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut criteria = Vec::new();
+        if self.side_count != 0 {
+            criteria.push(format!("sides={}", self.side_count));
+        }
+        if let Some(class) = self.class {
+            criteria.push(format!("class={}", class));
+        }
+        if self.minimum_area.is_some() || self.maximum_area.is_some() {
+            let bound = |bound: Option<f64>| bound.map(|b| b.to_string()).unwrap_or_default();
+            let (minimum, maximum) = (bound(self.minimum_area), bound(self.maximum_area));
+            criteria.push(format!("area={}..{}", minimum, maximum));
+        }
+        if let Some(([x, y, z], angle)) = self.normal {
+            criteria.push(format!("normal=[{},{},{}]<{}", x, y, z, angle));
+        }
+        let list = |values: &[u32]| -> String {
+            values.iter().map(u32::to_string).collect::<Vec<_>>().join(",")
+        };
+        if let Some(indices) = &self.indices {
+            criteria.push(format!("index=[{}]", list(indices)));
+        }
+        if let Some(seed_faces) = &self.seed_faces {
+            criteria.push(format!("seed=[{}]", list(seed_faces)));
+        }
+        write!(f, "{{{}}}", criteria.join(","))
+    }
+}
+
+// The area bounds and normal are checked to be finite, so are never NaN.
+impl Eq for FaceSelector {}
+
+impl Hash for FaceSelector {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.side_count.hash(state);
+        self.class.hash(state);
+        for bound in &[self.minimum_area, self.maximum_area] {
+            bound.is_some().hash(state);
+            bound.iter().for_each(|bound| hash_decimal(*bound, state));
+        }
+        self.normal.is_some().hash(state);
+        if let Some((direction, angle)) = self.normal {
+            direction.iter().for_each(|value| hash_decimal(*value, state));
+            hash_decimal(angle, state);
+        }
+        self.indices.hash(state);
+        self.seed_faces.hash(state);
+    }
+}
+
+/// Hashes a decimal parameter consistently with `==`, under which zero and negative zero are
+/// equal. Parameters are never NaN, since every constructor rejects it.
+fn hash_decimal<H: Hasher>(value: f64, state: &mut H) {
+    let value = if value == 0.0 { 0.0 } else { value };
+    value.to_bits().hash(state);
+}
+
+/// Collects the values into an ascending list without duplicates.
+fn sorted(values: impl IntoIterator<Item = u32>) -> Vec<u32> {
+    let mut values: Vec<u32> = values.into_iter().collect();
+    values.sort_unstable();
+    values.dedup();
+    values
+}
+
+/// Selects the vertices an operator acts upon.
+#[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Hash, Debug, Default)]
+pub struct VertexSelector {
+    degree: u32,
+}
+
+impl VertexSelector {
+    /// Only selects vertices where the given number of faces meet.
+    /// If `degree` is zero, every vertex is selected.
+    pub fn with_degree(degree: u32) -> Self {
+        VertexSelector { degree }
+    }
+
+    /// The number of faces that must meet at a vertex for it to be selected, or zero if any
+    /// number is allowed.
+    pub fn degree(&self) -> u32 {
+        self.degree
+    }
+}
+
+/// Selects the edges an operator acts upon, by the number of sides of the two faces they border.
+#[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Hash, Debug, Default)]
+pub struct EdgeSelector {
+    side_counts: (u32, u32),
+}
+
+impl EdgeSelector {
+    /// Only selects edges between a face with `first` sides and a face with `second` sides, in
+    /// either order. A side count of zero matches faces with any number of sides.
+    pub fn between(first: u32, second: u32) -> Self {
+        EdgeSelector {
+            side_counts: (first.min(second), first.max(second)),
         }
     }
+
+    /// Returns the side counts of the faces a selected edge borders, smallest first. Either may be
+    /// zero, which matches faces with any number of sides.
+    pub fn side_counts(&self) -> (u32, u32) {
+        self.side_counts
+    }
+
+    /// Returns whether an edge between faces with the given numbers of sides is selected.
+    pub fn matches(&self, first: u32, second: u32) -> bool {
+        let is_match = |expected: u32, actual: u32| expected == 0 || expected == actual;
+        let (a, b) = self.side_counts;
+        (is_match(a, first) && is_match(b, second)) || (is_match(a, second) && is_match(b, first))
+    }
 }
 
+/// The `ambo` operator, which replaces each vertex with a face and each edge with a vertex at its
+/// midpoint. The same as [`Operator::Ambo`], for applying it as a [`ConwayOperator`].
+///
+/// [`ConwayOperator`]: super::ConwayOperator
+#[derive(Copy, Clone, Default, PartialOrd, PartialEq, Eq, Hash, Debug)]
+pub struct Ambo;
+
+/// The `dual` operator, which swaps the vertices and faces. The same as [`Operator::Dual`], for
+/// applying it as a [`ConwayOperator`].
+///
+/// [`ConwayOperator`]: super::ConwayOperator
+#[derive(Copy, Clone, Default, PartialOrd, PartialEq, Eq, Hash, Debug)]
+pub struct Dual;
+
 /// The `kis` operator (short for triakis, also known as [Kleetope](https://en.wikipedia.org/wiki/Kleetope))
 /// replaces each n-sided face with a matching n-sided pyramid. For example, a hexagon becomes a
 /// hexagonal pyramid.
-#[derive(Copy, Clone, PartialOrd, PartialEq, Debug)]
+#[derive(Clone, PartialOrd, PartialEq, Debug)]
 pub struct Kis {
-    side_count: u32,
+    selector: FaceSelector,
     apex_scale: f64,
 }
 
@@ -39,7 +577,15 @@ impl Kis {
     /// If `side_count` is zero, every side will be operated upon.
     pub fn restrict_to_sides(side_count: u32) -> Self {
         Kis {
-            side_count,
+            selector: FaceSelector::default().with_sides(side_count),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a `kis` operator that will only act on faces matching the selector.
+    pub fn select_faces(selector: FaceSelector) -> Self {
+        Kis {
+            selector,
             ..Default::default()
         }
     }
@@ -61,16 +607,29 @@ impl Kis {
     /// # Restrictions
     /// Panics if `apex_scale` is NaN or infinite.
     pub fn restrict_to_sides_and_scale_apex(side_count: u32, apex_scale: f64) -> Self {
+        let selector = FaceSelector::default().with_sides(side_count);
+        Self::select_faces_and_scale_apex(selector, apex_scale)
+    }
+
+    /// Creates a `kis` operator with the given apex scale and will only act on faces matching the
+    /// selector.
+    /// # Restrictions
+    /// Panics if `apex_scale` is NaN or infinite.
+    pub fn select_faces_and_scale_apex(selector: FaceSelector, apex_scale: f64) -> Self {
         assert!(!apex_scale.is_nan(), "Apex scale must not be NaN.");
         assert!(apex_scale.is_finite(), "Apex scale must be finite.");
         Kis {
-            side_count,
+            selector,
             apex_scale,
         }
     }
 
     pub fn side_count(&self) -> u32 {
-        self.side_count
+        self.selector.side_count()
+    }
+
+    pub fn selector(&self) -> &FaceSelector {
+        &self.selector
     }
 
     /// This determines the height of the new apex for each affected face, by multiplying the scale
@@ -84,48 +643,1291 @@ impl Kis {
 impl Default for Kis {
     fn default() -> Self {
         Kis {
-            side_count: 0,
+            selector: FaceSelector::default(),
             apex_scale: 0.1,
         }
     }
 }
 
-use pest_derive::Parser;
-#[derive(Parser)]
-#[grammar = "polyhedrator/notation.pest"]
-struct NotationParser;
+// The apex scale is checked to be finite, so is never NaN.
+impl Eq for Kis {}
 
-impl Operator {
-    pub fn try_parse(value: &str) -> Result<Vec<Operator>, ()> {
-        use pest::Parser;
-
-        match NotationParser::parse(Rule::expression, value) {
-            Ok(pairs) => {
-                let mut operators = Vec::new();
-                for expression_pair in pairs {
-                    for operator_pair in expression_pair.into_inner() {
-                        let operator = match operator_pair.as_rule() {
-                            Rule::ambo => Operator::Ambo,
-                            Rule::dual => Operator::Dual,
-                            Rule::kis => {
-                                let parameter_pair = operator_pair.into_inner().next();
-                                if let Some(parameter_pair) = parameter_pair {
-                                    assert_eq!(parameter_pair.as_rule(), Rule::parameter);
-                                    let sides = parameter_pair.as_str().parse::<u32>().unwrap();
-                                    Operator::Kis(Kis::restrict_to_sides_and_scale_apex(sides, 0.0))
-                                }
-                                else {
-                                    Operator::Kis(Kis::scale_apex(0.0))
-                                }
-                            },
-                            _ => unreachable!(),
-                        };
-                        operators.push(operator);
-                    }
-                }
-                Ok(operators)
-            },
-            Err(_) => Err(())
+impl Hash for Kis {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.selector.hash(state);
+        hash_decimal(self.apex_scale, state);
+    }
+}
+
+/// The `truncate` operator cuts off each vertex, replacing it with a new face that has as many
+/// sides as the vertex had edges. For example, a truncated cube has its corners replaced with
+/// triangles.
+#[derive(Copy, Clone, PartialOrd, PartialEq, Debug)]
+pub struct Truncate {
+    selector: VertexSelector,
+    depth: f64,
+}
+
+impl Truncate {
+    /// Creates a `truncate` operator that will only act on vertices with the given degree.
+    /// If `degree` is zero, every vertex will be operated upon.
+    pub fn restrict_to_degree(degree: u32) -> Self {
+        Self::select_vertices(VertexSelector::with_degree(degree))
+    }
+
+    /// Creates a `truncate` operator that will only act on vertices matching the selector.
+    pub fn select_vertices(selector: VertexSelector) -> Self {
+        Truncate {
+            selector,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a `truncate` operator with the given cut depth.
+    /// # Restrictions
+    /// Panics if `depth` is not between zero and one half, exclusive.
+    pub fn cut_depth(depth: f64) -> Self {
+        Self::select_vertices_and_cut_depth(VertexSelector::default(), depth)
+    }
+
+    /// Creates a `truncate` operator with the given cut depth and will only act on vertices with
+    /// the given degree.
+    /// # Restrictions
+    /// Panics if `depth` is not between zero and one half, exclusive.
+    pub fn restrict_to_degree_and_cut_depth(degree: u32, depth: f64) -> Self {
+        Self::select_vertices_and_cut_depth(VertexSelector::with_degree(degree), depth)
+    }
+
+    /// Creates a `truncate` operator with the given cut depth and will only act on vertices
+    /// matching the selector.
+    /// # Restrictions
+    /// Panics if `depth` is not between zero and one half, exclusive.
+    pub fn select_vertices_and_cut_depth(selector: VertexSelector, depth: f64) -> Self {
+        assert!(depth > 0.0 && depth < 0.5, "Cut depth must be between zero and one half.");
+        Truncate { selector, depth }
+    }
+
+    pub fn degree(&self) -> u32 {
+        self.selector.degree()
+    }
+
+    pub fn selector(&self) -> VertexSelector {
+        self.selector
+    }
+
+    /// This determines where each affected vertex is cut, as a fraction of the length of each edge
+    /// leaving it.
+    /// This will always be between zero and one half, exclusive.
+    pub fn depth(&self) -> f64 {
+        self.depth
+    }
+}
+
+impl Default for Truncate {
+    fn default() -> Self {
+        Truncate {
+            selector: VertexSelector::default(),
+            depth: 1.0 / 3.0,
+        }
+    }
+}
+
+// The cut depth is checked to be between zero and one half, so is never NaN.
+impl Eq for Truncate {}
+
+impl Hash for Truncate {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.selector.hash(state);
+        hash_decimal(self.depth, state);
+    }
+}
+
+/// The `needle` operator is the dual of `truncate`, and is equivalent to applying `dual` then
+/// `kis`. Each edge is replaced by a pair of triangles meeting across a new edge between the
+/// centers of the faces it bordered.
+#[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Needle {
+    selector: VertexSelector,
+}
+
+impl Needle {
+    /// Creates a `needle` operator that will only act on faces of the dual with the given number of
+    /// sides, which are the vertices where that many faces meet.
+    /// If `side_count` is zero, every side will be operated upon.
+    pub fn restrict_to_sides(side_count: u32) -> Self {
+        Self::select_vertices(VertexSelector::with_degree(side_count))
+    }
+
+    /// Creates a `needle` operator that will only act on the faces of the dual standing in for the
+    /// vertices matching the selector.
+    pub fn select_vertices(selector: VertexSelector) -> Self {
+        Needle { selector }
+    }
+
+    pub fn side_count(&self) -> u32 {
+        self.selector.degree()
+    }
+
+    pub fn selector(&self) -> VertexSelector {
+        self.selector
+    }
+}
+
+/// The `zip` operator (also known as bitruncation) is the dual of `kis`, and is equivalent to
+/// applying `kis` then `dual`. Each n-sided face is replaced by a smaller n-sided face, and each
+/// vertex by a face with twice as many sides as it had edges.
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Zip {
+    selector: FaceSelector,
+}
+
+impl Zip {
+    /// Creates a `zip` operator that will only act on faces with the given number of sides.
+    /// If `side_count` is zero, every side will be operated upon.
+    pub fn restrict_to_sides(side_count: u32) -> Self {
+        Zip {
+            selector: FaceSelector::default().with_sides(side_count),
+        }
+    }
+
+    /// Creates a `zip` operator that will only act on faces matching the selector.
+    pub fn select_faces(selector: FaceSelector) -> Self {
+        Zip { selector }
+    }
+
+    pub fn side_count(&self) -> u32 {
+        self.selector.side_count()
+    }
+
+    pub fn selector(&self) -> &FaceSelector {
+        &self.selector
+    }
+}
+
+/// The `chamfer` operator bevels each edge, replacing it with a new hexagonal face. The original
+/// faces are shrunk and raised, but keep the same number of sides.
+#[derive(Copy, Clone, PartialOrd, PartialEq, Debug)]
+pub struct Chamfer {
+    selector: EdgeSelector,
+    ratio: f64,
+}
+
+impl Chamfer {
+    /// Creates a `chamfer` operator with the given ratio.
+    /// # Restrictions
+    /// Panics if `ratio` is not between zero and one, exclusive.
+    pub fn with_ratio(ratio: f64) -> Self {
+        Self::select_edges_with_ratio(EdgeSelector::default(), ratio)
+    }
+
+    /// Creates a `chamfer` operator that will only replace the edges matching the selector. The
+    /// remaining edges stay attached to their original vertices.
+    pub fn select_edges(selector: EdgeSelector) -> Self {
+        Chamfer {
+            selector,
+            ..Default::default()
         }
     }
+
+    /// Creates a `chamfer` operator with the given ratio that will only replace the edges matching
+    /// the selector.
+    /// # Restrictions
+    /// Panics if `ratio` is not between zero and one, exclusive.
+    pub fn select_edges_with_ratio(selector: EdgeSelector, ratio: f64) -> Self {
+        assert!(ratio > 0.0 && ratio < 1.0, "Ratio must be between zero and one.");
+        Chamfer { selector, ratio }
+    }
+
+    pub fn selector(&self) -> EdgeSelector {
+        self.selector
+    }
+
+    /// This determines how far the new hexagonal faces extend into each original face, as a
+    /// fraction of the distance from each vertex to the center of the face.
+    /// This will always be between zero and one, exclusive.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+}
+
+impl Default for Chamfer {
+    fn default() -> Self {
+        Chamfer {
+            selector: EdgeSelector::default(),
+            ratio: 0.5,
+        }
+    }
+}
+
+// The ratio is checked to be between zero and one, so is never NaN.
+impl Eq for Chamfer {}
+
+impl Hash for Chamfer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.selector.hash(state);
+        hash_decimal(self.ratio, state);
+    }
+}
+
+/// The `whirl` operator replaces each n-sided face with a smaller, twisted n-sided face
+/// surrounded by n hexagons.
+#[derive(Copy, Clone, PartialOrd, PartialEq, Debug)]
+pub struct Whirl {
+    chirality: Chirality,
+    ratio: f64,
+}
+
+impl Whirl {
+    /// Creates a `whirl` operator with the given handedness.
+    pub fn with_chirality(chirality: Chirality) -> Self {
+        Whirl {
+            chirality,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a `whirl` operator with the given twist ratio.
+    /// # Restrictions
+    /// Panics if `ratio` is not between zero and one, exclusive.
+    pub fn with_ratio(ratio: f64) -> Self {
+        assert!(ratio > 0.0 && ratio < 1.0, "Ratio must be between zero and one.");
+        Whirl {
+            ratio,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a `whirl` operator with the given handedness and twist ratio.
+    /// # Restrictions
+    /// Panics if `ratio` is not between zero and one, exclusive.
+    pub fn with_chirality_and_ratio(chirality: Chirality, ratio: f64) -> Self {
+        assert!(ratio > 0.0 && ratio < 1.0, "Ratio must be between zero and one.");
+        Whirl { chirality, ratio }
+    }
+
+    pub fn chirality(&self) -> Chirality {
+        self.chirality
+    }
+
+    /// This determines how far the vertices of each new inner face are placed from the center
+    /// of the original face, as a fraction of the distance to the nearest new vertex on its edge.
+    /// This will always be between zero and one, exclusive.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+}
+
+impl Default for Whirl {
+    fn default() -> Self {
+        Whirl {
+            chirality: Chirality::Right,
+            ratio: 1.0 / 3.0,
+        }
+    }
+}
+
+// The ratio is checked to be between zero and one, so is never NaN.
+impl Eq for Whirl {}
+
+impl Hash for Whirl {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.chirality.hash(state);
+        hash_decimal(self.ratio, state);
+    }
+}
+
+/// The `loft` operator augments each face with a prism, adding a smaller copy of each face joined
+/// to the original edges by trapezoids.
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Loft {
+    selector: FaceSelector,
+}
+
+impl Loft {
+    /// Creates a `loft` operator that will only act on faces with the given number of sides.
+    /// If `side_count` is zero, every side will be operated upon.
+    pub fn restrict_to_sides(side_count: u32) -> Self {
+        Loft {
+            selector: FaceSelector::default().with_sides(side_count),
+        }
+    }
+
+    /// Creates a `loft` operator that will only act on faces matching the selector.
+    pub fn select_faces(selector: FaceSelector) -> Self {
+        Loft { selector }
+    }
+
+    pub fn side_count(&self) -> u32 {
+        self.selector.side_count()
+    }
+
+    pub fn selector(&self) -> &FaceSelector {
+        &self.selector
+    }
+}
+
+/// The `lace` operator augments each face with an antiprism, adding a smaller, twisted copy of
+/// each face joined to the original edges by triangles.
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Lace {
+    selector: FaceSelector,
+}
+
+impl Lace {
+    /// Creates a `lace` operator that will only act on faces with the given number of sides.
+    /// If `side_count` is zero, every side will be operated upon.
+    pub fn restrict_to_sides(side_count: u32) -> Self {
+        Lace {
+            selector: FaceSelector::default().with_sides(side_count),
+        }
+    }
+
+    /// Creates a `lace` operator that will only act on faces matching the selector.
+    pub fn select_faces(selector: FaceSelector) -> Self {
+        Lace { selector }
+    }
+
+    pub fn side_count(&self) -> u32 {
+        self.selector.side_count()
+    }
+
+    pub fn selector(&self) -> &FaceSelector {
+        &self.selector
+    }
+}
+
+/// The `stake` operator subdivides each face into a ring of quadrilaterals around its center, and a
+/// ring of triangles along its edges.
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Stake {
+    selector: FaceSelector,
+}
+
+impl Stake {
+    /// Creates a `stake` operator that will only act on faces with the given number of sides.
+    /// If `side_count` is zero, every side will be operated upon.
+    pub fn restrict_to_sides(side_count: u32) -> Self {
+        Stake {
+            selector: FaceSelector::default().with_sides(side_count),
+        }
+    }
+
+    /// Creates a `stake` operator that will only act on faces matching the selector.
+    pub fn select_faces(selector: FaceSelector) -> Self {
+        Stake { selector }
+    }
+
+    pub fn side_count(&self) -> u32 {
+        self.selector.side_count()
+    }
+
+    pub fn selector(&self) -> &FaceSelector {
+        &self.selector
+    }
+}
+
+/// The `quinto` operator replaces each n-sided face with a smaller n-sided face surrounded by n
+/// pentagons, one for each original vertex.
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Quinto {
+    selector: FaceSelector,
+}
+
+impl Quinto {
+    /// Creates a `quinto` operator that will only act on faces with the given number of sides.
+    /// If `side_count` is zero, every side will be operated upon.
+    pub fn restrict_to_sides(side_count: u32) -> Self {
+        Quinto {
+            selector: FaceSelector::default().with_sides(side_count),
+        }
+    }
+
+    /// Creates a `quinto` operator that will only act on faces matching the selector.
+    pub fn select_faces(selector: FaceSelector) -> Self {
+        Quinto { selector }
+    }
+
+    pub fn side_count(&self) -> u32 {
+        self.selector.side_count()
+    }
+
+    pub fn selector(&self) -> &FaceSelector {
+        &self.selector
+    }
+}
+
+/// The `joined-lace` operator is similar to `lace`, but joins the triangles on either side of
+/// each original edge into a single quadrilateral.
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash, Debug, Default)]
+pub struct JoinedLace {
+    selector: FaceSelector,
+}
+
+impl JoinedLace {
+    /// Creates a `joined-lace` operator that will only act on faces with the given number of
+    /// sides.
+    /// If `side_count` is zero, every side will be operated upon.
+    pub fn restrict_to_sides(side_count: u32) -> Self {
+        JoinedLace {
+            selector: FaceSelector::default().with_sides(side_count),
+        }
+    }
+
+    /// Creates a `joined-lace` operator that will only act on faces matching the selector.
+    pub fn select_faces(selector: FaceSelector) -> Self {
+        JoinedLace { selector }
+    }
+
+    pub fn side_count(&self) -> u32 {
+        self.selector.side_count()
+    }
+
+    pub fn selector(&self) -> &FaceSelector {
+        &self.selector
+    }
+}
+
+/// The `cross` operator combines `kis` and subdivision, replacing each n-sided face with a ring of
+/// n quadrilaterals around its center and two triangles along each original edge.
+#[derive(Clone, PartialOrd, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Cross {
+    selector: FaceSelector,
+}
+
+impl Cross {
+    /// Creates a `cross` operator that will only act on faces with the given number of sides.
+    /// If `side_count` is zero, every side will be operated upon.
+    pub fn restrict_to_sides(side_count: u32) -> Self {
+        Cross {
+            selector: FaceSelector::default().with_sides(side_count),
+        }
+    }
+
+    /// Creates a `cross` operator that will only act on faces matching the selector.
+    pub fn select_faces(selector: FaceSelector) -> Self {
+        Cross { selector }
+    }
+
+    pub fn side_count(&self) -> u32 {
+        self.selector.side_count()
+    }
+
+    pub fn selector(&self) -> &FaceSelector {
+        &self.selector
+    }
+}
+
+/// The Goldberg-Coxeter operator subdivides each face along the triangular lattice (or the square
+/// lattice, if every face is a quadrilateral), so that each original edge becomes the lattice
+/// vector `(m, n)`. Faces that are neither triangles nor quadrilaterals are first divided into
+/// triangles around their centers.
+///
+/// Class I (`n` is zero) and class II (`m` equals `n`) subdivisions are symmetric, while class III
+/// subdivisions are chiral; swapping `m` and `n` produces the mirror image.
+/// See [https://en.wikipedia.org/wiki/Goldberg%E2%80%93Coxeter_construction](Goldberg-Coxeter
+/// construction) for more information.
+#[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Hash, Debug)]
+pub struct GoldbergCoxeter {
+    m: u32,
+    n: u32,
+}
+
+impl GoldbergCoxeter {
+    /// Creates a Goldberg-Coxeter operator with the lattice vector `(m, n)`.
+    /// # Restrictions
+    /// Panics if both `m` and `n` are zero.
+    pub fn new(m: u32, n: u32) -> Self {
+        assert!(m > 0 || n > 0, "Lattice vector must not be zero.");
+        GoldbergCoxeter { m, n }
+    }
+
+    /// Creates a class I Goldberg-Coxeter operator, which divides each edge into `frequency`
+    /// equal parts.
+    /// # Restrictions
+    /// Panics if `frequency` is zero.
+    pub fn subdivide(frequency: u32) -> Self {
+        Self::new(frequency, 0)
+    }
+
+    pub fn m(&self) -> u32 {
+        self.m
+    }
+
+    pub fn n(&self) -> u32 {
+        self.n
+    }
+
+    /// Returns the handedness of the subdivision, or `None` if it is not chiral.
+    pub fn chirality(&self) -> Option<Chirality> {
+        if self.m == 0 || self.n == 0 || self.m == self.n {
+            None
+        }
+        else if self.m > self.n {
+            Some(Chirality::Right)
+        }
+        else {
+            Some(Chirality::Left)
+        }
+    }
+}
+
+impl Default for GoldbergCoxeter {
+    fn default() -> Self {
+        GoldbergCoxeter { m: 2, n: 0 }
+    }
+}
+
+/// The `inset` operator shrinks each face towards its center, joining the smaller copy to the
+/// original edges with a ring of quadrilaterals. The smaller copy may also be raised or sunken
+/// along the face's normal.
+#[derive(Clone, PartialOrd, PartialEq, Debug)]
+pub struct Inset {
+    selector: FaceSelector,
+    ratio: f64,
+    distance: f64,
+}
+
+impl Inset {
+    /// Creates an `inset` operator that will only act on faces with the given number of sides.
+    /// If `side_count` is zero, every side will be operated upon.
+    pub fn restrict_to_sides(side_count: u32) -> Self {
+        Inset {
+            selector: FaceSelector::default().with_sides(side_count),
+            ..Default::default()
+        }
+    }
+
+    /// Creates an `inset` operator that will only act on faces matching the selector.
+    pub fn select_faces(selector: FaceSelector) -> Self {
+        Inset {
+            selector,
+            ..Default::default()
+        }
+    }
+
+    /// Creates an `inset` operator with the given inset ratio and distance.
+    /// # Restrictions
+    /// Panics if `ratio` is not between zero and one, exclusive, or if `distance` is NaN or
+    /// infinite.
+    pub fn with_ratio_and_distance(ratio: f64, distance: f64) -> Self {
+        Self::select_faces_with_ratio_and_distance(FaceSelector::default(), ratio, distance)
+    }
+
+    /// Creates an `inset` operator that will only act on faces with the given number of sides,
+    /// with the given inset ratio and distance.
+    /// # Restrictions
+    /// Panics if `ratio` is not between zero and one, exclusive, or if `distance` is NaN or
+    /// infinite.
+    pub fn restrict_to_sides_with_ratio_and_distance(
+        side_count: u32,
+        ratio: f64,
+        distance: f64,
+    ) -> Self {
+        let selector = FaceSelector::default().with_sides(side_count);
+        Self::select_faces_with_ratio_and_distance(selector, ratio, distance)
+    }
+
+    /// Creates an `inset` operator that will only act on faces matching the selector, with the
+    /// given inset ratio and distance.
+    /// # Restrictions
+    /// Panics if `ratio` is not between zero and one, exclusive, or if `distance` is NaN or
+    /// infinite.
+    pub fn select_faces_with_ratio_and_distance(
+        selector: FaceSelector,
+        ratio: f64,
+        distance: f64,
+    ) -> Self {
+        assert!(ratio > 0.0 && ratio < 1.0, "Ratio must be between zero and one.");
+        assert!(distance.is_finite(), "Distance must be finite.");
+        Inset {
+            selector,
+            ratio,
+            distance,
+        }
+    }
+
+    pub fn side_count(&self) -> u32 {
+        self.selector.side_count()
+    }
+
+    pub fn selector(&self) -> &FaceSelector {
+        &self.selector
+    }
+
+    /// This determines how far each vertex of the smaller face is moved towards the center of the
+    /// original face, as a fraction of the distance between them.
+    /// This will always be between zero and one, exclusive.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// This determines how far the smaller face is moved along the normal of the original face,
+    /// as a fraction of the mean distance from its vertices to its center. Negative distances sink
+    /// the face inwards.
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+}
+
+impl Default for Inset {
+    fn default() -> Self {
+        Inset {
+            selector: FaceSelector::default(),
+            ratio: 0.5,
+            distance: 0.0,
+        }
+    }
+}
+
+// The ratio and distance are checked to be finite, so are never NaN.
+impl Eq for Inset {}
+
+impl Hash for Inset {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.selector.hash(state);
+        hash_decimal(self.ratio, state);
+        hash_decimal(self.distance, state);
+    }
+}
+
+/// The `extrude` operator pushes each face outward along its normal, joining it to the original
+/// edges with a ring of quadrilaterals. The pushed face may also be shrunk towards its center.
+#[derive(Clone, PartialOrd, PartialEq, Debug)]
+pub struct Extrude {
+    selector: FaceSelector,
+    distance: f64,
+    ratio: f64,
+}
+
+impl Extrude {
+    /// Creates an `extrude` operator that will only act on faces with the given number of sides.
+    /// If `side_count` is zero, every side will be operated upon.
+    pub fn restrict_to_sides(side_count: u32) -> Self {
+        Extrude {
+            selector: FaceSelector::default().with_sides(side_count),
+            ..Default::default()
+        }
+    }
+
+    /// Creates an `extrude` operator that will only act on faces matching the selector.
+    pub fn select_faces(selector: FaceSelector) -> Self {
+        Extrude {
+            selector,
+            ..Default::default()
+        }
+    }
+
+    /// Creates an `extrude` operator with the given distance and inset ratio.
+    /// # Restrictions
+    /// Panics if `distance` is zero, NaN or infinite, or if `ratio` is not between zero,
+    /// inclusive, and one, exclusive.
+    pub fn with_distance_and_ratio(distance: f64, ratio: f64) -> Self {
+        Self::select_faces_with_distance_and_ratio(FaceSelector::default(), distance, ratio)
+    }
+
+    /// Creates an `extrude` operator that will only act on faces with the given number of sides,
+    /// with the given distance and inset ratio.
+    /// # Restrictions
+    /// Panics if `distance` is zero, NaN or infinite, or if `ratio` is not between zero,
+    /// inclusive, and one, exclusive.
+    pub fn restrict_to_sides_with_distance_and_ratio(
+        side_count: u32,
+        distance: f64,
+        ratio: f64,
+    ) -> Self {
+        let selector = FaceSelector::default().with_sides(side_count);
+        Self::select_faces_with_distance_and_ratio(selector, distance, ratio)
+    }
+
+    /// Creates an `extrude` operator that will only act on faces matching the selector, with the
+    /// given distance and inset ratio.
+    /// # Restrictions
+    /// Panics if `distance` is zero, NaN or infinite, or if `ratio` is not between zero,
+    /// inclusive, and one, exclusive.
+    pub fn select_faces_with_distance_and_ratio(
+        selector: FaceSelector,
+        distance: f64,
+        ratio: f64,
+    ) -> Self {
+        assert!(distance.is_finite(), "Distance must be finite.");
+        assert!(distance != 0.0, "Distance must not be zero.");
+        assert!((0.0..1.0).contains(&ratio), "Ratio must be at least zero and less than one.");
+        Extrude {
+            selector,
+            distance,
+            ratio,
+        }
+    }
+
+    pub fn side_count(&self) -> u32 {
+        self.selector.side_count()
+    }
+
+    pub fn selector(&self) -> &FaceSelector {
+        &self.selector
+    }
+
+    /// This determines how far each face is pushed along its normal, as a fraction of the mean
+    /// distance from its vertices to its center. Negative distances push the face inwards.
+    /// This will never be zero.
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    /// This determines how far each vertex of the pushed face is moved towards its center, as a
+    /// fraction of the distance between them.
+    /// This will always be at least zero and less than one.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+}
+
+impl Default for Extrude {
+    fn default() -> Self {
+        Extrude {
+            selector: FaceSelector::default(),
+            distance: 0.5,
+            ratio: 0.0,
+        }
+    }
+}
+
+// The distance and ratio are checked to be finite, so are never NaN.
+impl Eq for Extrude {}
+
+impl Hash for Extrude {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.selector.hash(state);
+        hash_decimal(self.distance, state);
+        hash_decimal(self.ratio, state);
+    }
+}
+
+impl Operator {
+    /// Parses a sequence of operators from Conway notation, in the order they are written. Only
+    /// macros defined within the notation itself may be used; see `NotationContext` to define
+    /// others.
+    pub fn try_parse(value: &str) -> Result<Vec<Operator>, NotationError> {
+        NotationContext::default().parse_operators(value)
+    }
+}
+
+/// Parses a single operator of Conway notation.
+pub(super) fn parse_operator(operator_pair: Pair<Rule>) -> Result<Operator, NotationError> {
+    let span = operator_pair.as_span();
+    let rule = operator_pair.as_rule();
+    build_operator(rule, span, Parts::new(operator_pair.into_inner()))
+}
+
+/// Parses a single operator of the verbose dialect, such as `kis(5, h=0.2)`.
+pub(super) fn parse_verbose_operator(
+    operator_pair: Pair<Rule>,
+) -> Result<Operator, NotationError> {
+    let span = operator_pair.as_span();
+    let mut pairs = operator_pair.into_inner();
+    let name_pair = pairs.next().unwrap();
+    let name = name_pair.as_str().replace('_', "-");
+    match OPERATOR_RULES.iter().find(|rule| verbose_name(**rule) == name) {
+        Some(rule) => build_operator(*rule, span, Parts::new(pairs)),
+        None => Err(NotationError::invalid(name_pair.as_span(), "unknown operator")),
+    }
+}
+
+/// The rules of every operator, in the order they are listed in the grammar.
+const OPERATOR_RULES: [Rule; 27] = [
+    Rule::ambo,
+    Rule::dual,
+    Rule::kis,
+    Rule::truncate,
+    Rule::join,
+    Rule::ortho,
+    Rule::expand,
+    Rule::gyro,
+    Rule::snub,
+    Rule::bevel,
+    Rule::meta,
+    Rule::needle,
+    Rule::zip,
+    Rule::propeller,
+    Rule::reflect,
+    Rule::goldberg_coxeter,
+    Rule::chamfer,
+    Rule::whirl,
+    Rule::loft,
+    Rule::joined_lace,
+    Rule::lace,
+    Rule::stake,
+    Rule::quinto,
+    Rule::cross,
+    Rule::subdivide,
+    Rule::inset,
+    Rule::extrude,
+];
+
+/// Returns the name of an operator in the verbose dialect.
+fn verbose_name(rule: Rule) -> &'static str {
+    match rule {
+        Rule::ambo => "ambo",
+        Rule::dual => "dual",
+        Rule::kis => "kis",
+        Rule::truncate => "truncate",
+        Rule::join => "join",
+        Rule::ortho => "ortho",
+        Rule::expand => "expand",
+        Rule::gyro => "gyro",
+        Rule::snub => "snub",
+        Rule::bevel => "bevel",
+        Rule::meta => "meta",
+        Rule::needle => "needle",
+        Rule::zip => "zip",
+        Rule::propeller => "propeller",
+        Rule::reflect => "reflect",
+        Rule::goldberg_coxeter => "goldberg-coxeter",
+        Rule::chamfer => "chamfer",
+        Rule::whirl => "whirl",
+        Rule::loft => "loft",
+        Rule::joined_lace => "joined-lace",
+        Rule::lace => "lace",
+        Rule::stake => "stake",
+        Rule::quinto => "quinto",
+        Rule::cross => "cross",
+        Rule::subdivide => "subdivide",
+        Rule::inset => "inset",
+        Rule::extrude => "extrude",
+        _ => unreachable!(),
+    }
+}
+
+/// What an operator accepts besides its symbol.
+struct Signature {
+    /// The most integers the operator accepts, such as a side count.
+    counts: usize,
+    is_chiral: bool,
+    selects_faces: bool,
+    selects_edges: bool,
+    /// The names of the decimal arguments the operator accepts, in order.
+    arguments: &'static [&'static str],
+}
+
+const NO_PARAMETERS: Signature = Signature {
+    counts: 0,
+    is_chiral: false,
+    selects_faces: false,
+    selects_edges: false,
+    arguments: &[],
+};
+
+fn signature(rule: Rule) -> Signature {
+    let selection = Signature {
+        counts: 1,
+        selects_faces: true,
+        ..NO_PARAMETERS
+    };
+    match rule {
+        Rule::kis => Signature {
+            arguments: &["h"],
+            ..selection
+        },
+        Rule::truncate => Signature {
+            counts: 1,
+            arguments: &["d"],
+            ..NO_PARAMETERS
+        },
+        Rule::gyro | Rule::snub | Rule::propeller => Signature {
+            is_chiral: true,
+            ..NO_PARAMETERS
+        },
+        Rule::needle | Rule::subdivide => Signature {
+            counts: 1,
+            ..NO_PARAMETERS
+        },
+        Rule::goldberg_coxeter => Signature {
+            counts: 2,
+            ..NO_PARAMETERS
+        },
+        Rule::chamfer => Signature {
+            selects_edges: true,
+            arguments: &["r"],
+            ..NO_PARAMETERS
+        },
+        Rule::whirl => Signature {
+            is_chiral: true,
+            arguments: &["r"],
+            ..NO_PARAMETERS
+        },
+        Rule::zip
+        | Rule::loft
+        | Rule::lace
+        | Rule::stake
+        | Rule::quinto
+        | Rule::joined_lace
+        | Rule::cross => selection,
+        Rule::inset => Signature {
+            arguments: &["r", "d"],
+            ..selection
+        },
+        Rule::extrude => Signature {
+            arguments: &["d", "r"],
+            ..selection
+        },
+        _ => NO_PARAMETERS,
+    }
+}
+
+/// The parts of an operator following its symbol or name, in either dialect.
+struct Parts<'i> {
+    counts: Vec<Pair<'i, Rule>>,
+    left_handed: Option<Pair<'i, Rule>>,
+    selector: Option<Pair<'i, Rule>>,
+    edge_selector: Option<Pair<'i, Rule>>,
+    arguments: Vec<Pair<'i, Rule>>,
+}
+
+impl<'i> Parts<'i> {
+    fn new(pairs: Pairs<'i, Rule>) -> Self {
+        let mut parts = Parts {
+            counts: Vec::new(),
+            left_handed: None,
+            selector: None,
+            edge_selector: None,
+            arguments: Vec::new(),
+        };
+        for pair in pairs {
+            parts.add(pair);
+        }
+        parts
+    }
+
+    fn add(&mut self, pair: Pair<'i, Rule>) {
+        match pair.as_rule() {
+            Rule::parameter => self.counts.push(pair),
+            Rule::left_handed => self.left_handed = Some(pair),
+            Rule::selector => self.selector = Some(pair),
+            Rule::edge_selector => self.edge_selector = Some(pair),
+            Rule::argument => self.arguments.push(pair),
+            Rule::arguments | Rule::verbose_arguments => {
+                for pair in pair.into_inner() {
+                    self.add(pair);
+                }
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns an error if the operator does not accept any of the parts. The compact dialect
+    /// only allows the parts each operator accepts, but the verbose dialect allows any.
+    fn check(&self, signature: &Signature) -> Result<(), NotationError> {
+        if let Some(pair) = self.counts.get(signature.counts) {
+            let message = match signature.counts {
+                0 => "operator does not accept integers".into(),
+                1 => "operator accepts only one integer".into(),
+                counts => format!("operator accepts only {} integers", counts),
+            };
+            return Err(NotationError::invalid(pair.as_span(), &message));
+        }
+        if let Some(pair) = &self.left_handed {
+            if !signature.is_chiral {
+                return Err(NotationError::invalid(pair.as_span(), "operator is not chiral"));
+            }
+        }
+        if let Some(pair) = &self.selector {
+            if !signature.selects_faces {
+                let message = "operator does not select faces";
+                return Err(NotationError::invalid(pair.as_span(), message));
+            }
+        }
+        if let Some(pair) = &self.edge_selector {
+            if !signature.selects_edges {
+                let message = "operator does not select edges";
+                return Err(NotationError::invalid(pair.as_span(), message));
+            }
+        }
+        if let Some(pair) = self.arguments.first() {
+            if signature.arguments.is_empty() {
+                let message = "operator does not accept decimals";
+                return Err(NotationError::invalid(pair.as_span(), message));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the first integer, returning zero if it is absent.
+    fn count(&self) -> Result<u32, NotationError> {
+        match self.counts.first() {
+            Some(pair) => parse_integer(pair.clone()),
+            None => Ok(0),
+        }
+    }
+
+    fn chirality(&self) -> Chirality {
+        match self.left_handed {
+            Some(_) => Chirality::Left,
+            None => Chirality::Right,
+        }
+    }
+
+    /// Parses the faces the operator selects, given either as a side count or a selector.
+    fn face_selector(&self) -> Result<FaceSelector, NotationError> {
+        let selector = FaceSelector::default().with_sides(self.count()?);
+        match &self.selector {
+            Some(pair) => parse_selector(pair.clone(), selector),
+            None => Ok(selector),
+        }
+    }
+}
+
+/// Builds an operator from its parts, checking that they are accepted and in range.
+fn build_operator(rule: Rule, span: Span, parts: Parts) -> Result<Operator, NotationError> {
+    let signature = signature(rule);
+    parts.check(&signature)?;
+    let values = parse_arguments(&parts.arguments, signature.arguments)?;
+    let operator = match rule {
+        Rule::ambo => Operator::Ambo,
+        Rule::dual => Operator::Dual,
+        Rule::kis => {
+            let apex_scale = values[0].unwrap_or(0.0);
+            Operator::Kis(Kis::select_faces_and_scale_apex(parts.face_selector()?, apex_scale))
+        },
+        Rule::truncate => {
+            let depth = values[0].unwrap_or_else(|| Truncate::default().depth);
+            if !(depth > 0.0 && depth < 0.5) {
+                let message = "truncation depth must be between 0 and one half";
+                return Err(NotationError::invalid(span, message));
+            }
+            let selector = VertexSelector::with_degree(parts.count()?);
+            Operator::Truncate(Truncate::select_vertices_and_cut_depth(selector, depth))
+        },
+        Rule::join => Operator::Join,
+        Rule::ortho => Operator::Ortho,
+        Rule::expand => Operator::Expand,
+        Rule::gyro => Operator::Gyro(parts.chirality()),
+        Rule::snub => Operator::Snub(parts.chirality()),
+        Rule::bevel => Operator::Bevel,
+        Rule::meta => Operator::Meta,
+        Rule::propeller => Operator::Propeller(parts.chirality()),
+        Rule::reflect => Operator::Reflect,
+        Rule::chamfer => {
+            let mut chamfer = Chamfer::default();
+            if let Some(pair) = parts.edge_selector {
+                let mut counts = pair.into_inner();
+                let first = parse_integer(counts.next().unwrap())?;
+                let second = parse_integer(counts.next().unwrap())?;
+                chamfer.selector = EdgeSelector::between(first, second);
+            }
+            if let Some(ratio) = values[0] {
+                if !(ratio > 0.0 && ratio < 1.0) {
+                    let message = "chamfer ratio must be between 0 and 1";
+                    return Err(NotationError::invalid(span, message));
+                }
+                chamfer.ratio = ratio;
+            }
+            Operator::Chamfer(chamfer)
+        },
+        Rule::whirl => {
+            let ratio = values[0].unwrap_or(Whirl::default().ratio);
+            if !(ratio > 0.0 && ratio < 1.0) {
+                let message = "whirl ratio must be between 0 and 1";
+                return Err(NotationError::invalid(span, message));
+            }
+            Operator::Whirl(Whirl {
+                chirality: parts.chirality(),
+                ratio,
+            })
+        },
+        Rule::needle => Operator::Needle(Needle::restrict_to_sides(parts.count()?)),
+        Rule::zip => Operator::Zip(Zip::select_faces(parts.face_selector()?)),
+        Rule::loft => Operator::Loft(Loft::select_faces(parts.face_selector()?)),
+        Rule::lace => Operator::Lace(Lace::select_faces(parts.face_selector()?)),
+        Rule::stake => Operator::Stake(Stake::select_faces(parts.face_selector()?)),
+        Rule::quinto => Operator::Quinto(Quinto::select_faces(parts.face_selector()?)),
+        Rule::joined_lace => {
+            Operator::JoinedLace(JoinedLace::select_faces(parts.face_selector()?))
+        },
+        Rule::cross => Operator::Cross(Cross::select_faces(parts.face_selector()?)),
+        Rule::subdivide => match parts.counts.first() {
+            None => Operator::GoldbergCoxeter(GoldbergCoxeter::default()),
+            Some(_) => {
+                let frequency = parts.count()?;
+                if frequency == 0 {
+                    let message = "Goldberg-Coxeter parameters must not both be zero";
+                    return Err(NotationError::invalid(span, message));
+                }
+                Operator::GoldbergCoxeter(GoldbergCoxeter::subdivide(frequency))
+            },
+        },
+        Rule::goldberg_coxeter => {
+            if parts.counts.len() < 2 {
+                return Err(NotationError::invalid(span, "operator requires two integers"));
+            }
+            let mut parameters = parts.counts.into_iter().map(parse_integer);
+            let m = parameters.next().unwrap()?;
+            let n = parameters.next().unwrap()?;
+            if m == 0 && n == 0 {
+                let message = "Goldberg-Coxeter parameters must not both be zero";
+                return Err(NotationError::invalid(span, message));
+            }
+            Operator::GoldbergCoxeter(GoldbergCoxeter::new(m, n))
+        },
+        Rule::inset => {
+            let default = Inset::default();
+            let ratio = values[0].unwrap_or(default.ratio);
+            let distance = values[1].unwrap_or(default.distance);
+            if !(ratio > 0.0 && ratio < 1.0) {
+                return Err(NotationError::invalid(span, "inset ratio must be between 0 and 1"));
+            }
+            Operator::Inset(Inset::select_faces_with_ratio_and_distance(
+                parts.face_selector()?,
+                ratio,
+                distance,
+            ))
+        },
+        Rule::extrude => {
+            let default = Extrude::default();
+            let distance = values[0].unwrap_or(default.distance);
+            let ratio = values[1].unwrap_or(default.ratio);
+            if distance == 0.0 {
+                return Err(NotationError::invalid(span, "extrusion distance must not be zero"));
+            }
+            if !(0.0..1.0).contains(&ratio) {
+                let message = "extrusion ratio must be at least 0 and less than 1";
+                return Err(NotationError::invalid(span, message));
+            }
+            Operator::Extrude(Extrude::select_faces_with_distance_and_ratio(
+                parts.face_selector()?,
+                distance,
+                ratio,
+            ))
+        },
+        _ => unreachable!(),
+    };
+    Ok(operator)
+}
+
+/// Parses the decimal arguments of an operator, given the names of the parameters it accepts in
+/// order, and returns the value given for each parameter. Arguments may be given by position,
+/// e.g. `i(0.3,0.1)`, or by name, e.g. `i(d=0.1)`, but positional arguments must come first.
+fn parse_arguments(
+    argument_pairs: &[Pair<Rule>],
+    names: &[&str],
+) -> Result<Vec<Option<f64>>, NotationError> {
+    let mut values = vec![None; names.len()];
+    let mut is_named = false;
+    for (position, argument_pair) in argument_pairs.iter().enumerate() {
+        let span = argument_pair.as_span();
+        let mut pairs = argument_pair.clone().into_inner().peekable();
+        let index = match pairs.peek().map(|pair| pair.as_rule()) {
+            Some(Rule::argument_name) => {
+                is_named = true;
+                let name = pairs.next().unwrap().as_str();
+                match names.iter().position(|parameter| *parameter == name) {
+                    Some(index) => index,
+                    None => {
+                        let message = format!("unknown parameter, expected {}", names.join(", "));
+                        return Err(NotationError::invalid(span, &message));
+                    },
+                }
+            },
+            _ if is_named => {
+                let message = "positional arguments must come before named arguments";
+                return Err(NotationError::invalid(span, message));
+            },
+            _ if position >= names.len() => {
+                let message = format!("too many arguments, expected at most {}", names.len());
+                return Err(NotationError::invalid(span, &message));
+            },
+            _ => position,
+        };
+
+        if values[index].is_some() {
+            return Err(NotationError::invalid(span, "parameter is given more than once"));
+        }
+        values[index] = Some(parse_decimal(pairs.next().unwrap())?);
+    }
+    Ok(values)
+}
+
+/// Adds the criteria of a selector to the given selector. Each criterion may only be given once.
+fn parse_selector(
+    selector_pair: Pair<Rule>,
+    selector: FaceSelector,
+) -> Result<FaceSelector, NotationError> {
+    let mut selector = selector;
+    for criterion_pair in selector_pair.into_inner() {
+        let span = criterion_pair.as_span();
+        let rule = criterion_pair.as_rule();
+        let mut pairs = criterion_pair.into_inner();
+        selector = match rule {
+            Rule::sides_criterion if selector.side_count == 0 => {
+                selector.with_sides(parse_integer(pairs.next().unwrap())?)
+            },
+            Rule::class_criterion if selector.class.is_none() => {
+                selector.with_class(parse_integer(pairs.next().unwrap())?)
+            },
+            Rule::area_criterion if selector.area() == (None, None) => {
+                let (mut minimum, mut maximum) = (None, None);
+                for pair in pairs {
+                    match pair.as_rule() {
+                        Rule::minimum => minimum = Some(parse_decimal(pair)?),
+                        Rule::maximum => maximum = Some(parse_decimal(pair)?),
+                        _ => unreachable!(),
+                    }
+                }
+                if let (Some(minimum), Some(maximum)) = (minimum, maximum) {
+                    if minimum > maximum {
+                        return Err(NotationError::invalid(
+                            span,
+                            "minimum area must not exceed the maximum",
+                        ));
+                    }
+                }
+                selector.with_area(minimum, maximum)
+            },
+            Rule::normal_criterion if selector.normal.is_none() => {
+                let values: Result<Vec<f64>, _> = pairs.map(parse_decimal).collect();
+                let values = values?;
+                let direction = Vector3::new(values[0], values[1], values[2]);
+                let angle = values[3];
+                // Directions too short or too long to measure are rejected along with zero
+                let length = direction.magnitude();
+                if !(length.is_finite() && length > 0.0) {
+                    return Err(NotationError::invalid(span, "normal direction must not be zero"));
+                }
+                if angle > 180.0 {
+                    return Err(NotationError::invalid(
+                        span,
+                        "normal angle must not exceed 180 degrees",
+                    ));
+                }
+                selector.with_normal(direction, angle)
+            },
+            Rule::index_criterion if selector.indices.is_none() => {
+                let indices: Result<Vec<u32>, _> = pairs.map(parse_integer).collect();
+                selector.with_indices(indices?)
+            },
+            Rule::seed_criterion if selector.seed_faces.is_none() => {
+                let seed_faces: Result<Vec<u32>, _> = pairs.map(parse_integer).collect();
+                selector.with_seed_faces(seed_faces?)
+            },
+            _ => {
+                return Err(NotationError::invalid(span, "criterion is given more than once"));
+            },
+        };
+    }
+    Ok(selector)
+}
+
+pub(super) fn parse_integer(pair: Pair<Rule>) -> Result<u32, NotationError> {
+    assert_eq!(pair.as_rule(), Rule::parameter);
+    pair.as_str()
+        .parse::<u32>()
+        .map_err(|_| NotationError::invalid(pair.as_span(), "integer is too large"))
+}
+
+fn parse_decimal(pair: Pair<Rule>) -> Result<f64, NotationError> {
+    // Decimals too long to represent exactly are rounded, but those too large are infinite
+    let value = pair.as_str().parse::<f64>().unwrap();
+    if !value.is_finite() {
+        return Err(NotationError::invalid(pair.as_span(), "decimal is too large"));
+    }
+    Ok(value)
 }
@@ -0,0 +1,127 @@
+//! Reports the progress of a build to an observer, and lets a caller cancel it part way through.
+//!
+//! Operators assemble their faces with a `Builder`, which reports each face to the observer of the
+//! build, and ignores whatever it is given once the build is cancelled. Builds that are not
+//! observed report nothing.
+
+use super::counts::SizeError;
+use super::{Operator, Polyhedron};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Receives the progress of a build. Every method does nothing by default.
+pub trait Observer {
+    /// Called before an operator is applied, with its index among the operators being applied.
+    fn operator_started(&mut self, _index: usize, _operator: &Operator) {}
+
+    /// Called after each face of the polyhedron being built is assembled, with the number
+    /// assembled so far and the number in total. Operators made of several simpler operators,
+    /// such as Goldberg-Coxeter operators dividing faces into triangles before dividing those,
+    /// assemble their faces more than once.
+    fn face_built(&mut self, _built: usize, _total: usize) {}
+
+    /// Called after an operator has been applied, with the polyhedron it produced.
+    fn operator_finished(&mut self, _index: usize, _operator: &Operator, _polyhedron: &Polyhedron) {
+    }
+}
+
+/// An observer which ignores the progress of the build.
+impl Observer for () {}
+
+/// A shared flag which cancels the builds it is given. Clones share the same flag, so a build
+/// may be cancelled from another thread, or by its own observer.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    is_cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels every build using this token. A build stops adding edges and faces as soon as it
+    /// notices, and returns [`BuildError::Cancelled`] once the operator being applied returns.
+    pub fn cancel(&self) {
+        self.is_cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.is_cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// An error from an observed build.
+#[derive(Clone, Debug)]
+pub enum BuildError {
+    /// The polyhedron was predicted to exceed the size limit, so nothing was built.
+    TooLarge(SizeError),
+    /// The build was cancelled by its token.
+    Cancelled,
+}
+
+impl From<SizeError> for BuildError {
+    fn from(error: SizeError) -> Self {
+        BuildError::TooLarge(error)
+    }
+}
+
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::TooLarge(error) => error.fmt(f),
+            BuildError::Cancelled => f.write_str("the build was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// The observer and token of an observed build, shared by every builder the build uses. The
+/// default reports nothing and is never cancelled.
+#[derive(Clone, Default)]
+pub(super) struct Progress {
+    observer: Option<Rc<RefCell<dyn Observer>>>,
+    token: Option<CancellationToken>,
+}
+
+impl Progress {
+    pub(super) fn new(observer: impl Observer + 'static, token: CancellationToken) -> Self {
+        Progress {
+            observer: Some(Rc::new(RefCell::new(observer))),
+            token: Some(token),
+        }
+    }
+
+    pub(super) fn is_cancelled(&self) -> bool {
+        match &self.token {
+            Some(token) => token.is_cancelled(),
+            None => false,
+        }
+    }
+
+    pub(super) fn operator_started(&self, index: usize, operator: &Operator) {
+        if let Some(observer) = &self.observer {
+            observer.borrow_mut().operator_started(index, operator);
+        }
+    }
+
+    pub(super) fn face_built(&self, built: usize, total: usize) {
+        if let Some(observer) = &self.observer {
+            observer.borrow_mut().face_built(built, total);
+        }
+    }
+
+    pub(super) fn operator_finished(
+        &self,
+        index: usize,
+        operator: &Operator,
+        polyhedron: &Polyhedron,
+    ) {
+        if let Some(observer) = &self.observer {
+            observer.borrow_mut().operator_finished(index, operator, polyhedron);
+        }
+    }
+}
@@ -1,26 +1,87 @@
 mod platonic;
+mod polygonal;
 
 pub use platonic::Platonic;
 
-use super::{Face, Polyhedron};
+use super::counts::Histogram;
+use super::{Counts, Face, Polyhedron, Vertex};
 
 #[derive(Copy, Clone, Debug, Hash, Eq, Ord, PartialOrd, PartialEq)]
 pub enum Seed {
     Platonic(Platonic),
+    /// A prism with the given number of sides, which must be at least 3.
+    Prism(u32),
+    /// An antiprism with the given number of sides, which must be at least 3.
+    Antiprism(u32),
+    /// A pyramid with the given number of sides, which must be at least 3.
+    Pyramid(u32),
 }
 
 impl Seed {
     pub fn polyhedron(self, edge_length: f64) -> Polyhedron {
         match self {
-            Seed::Platonic(platonic) => Platonic::polyhedron(platonic, edge_length)
+            Seed::Platonic(platonic) => Platonic::polyhedron(platonic, edge_length),
+            Seed::Prism(sides) => polygonal::prism(sides, edge_length),
+            Seed::Antiprism(sides) => polygonal::antiprism(sides, edge_length),
+            Seed::Pyramid(sides) => polygonal::pyramid(sides, edge_length),
         }
     }
+
+    /// Counts the vertices, edges and faces of the seed, without building it.
+    pub fn counts(self) -> Counts {
+        let (vertices, edges, faces, vertex_degrees, face_sides) = match self {
+            Seed::Platonic(platonic) => {
+                let (vertices, edges, faces, degree, sides) = match platonic {
+                    Platonic::Tetrahedron => (4, 6, 4, 3, 3),
+                    Platonic::Cube => (8, 12, 6, 3, 4),
+                    Platonic::Octahedron => (6, 12, 8, 4, 3),
+                    Platonic::Dodecahedron => (20, 30, 12, 3, 5),
+                    Platonic::Icosahedron => (12, 30, 20, 5, 3),
+                };
+                let degrees = histogram(&[(degree, vertices)]);
+                (vertices, edges, faces, degrees, histogram(&[(sides, faces)]))
+            },
+            Seed::Prism(n) => {
+                let count = n as usize;
+                let degrees = histogram(&[(3, 2 * count)]);
+                let sides = histogram(&[(4, count), (n, 2)]);
+                (2 * count, 3 * count, count + 2, degrees, sides)
+            },
+            Seed::Antiprism(n) => {
+                let count = n as usize;
+                let degrees = histogram(&[(4, 2 * count)]);
+                let sides = histogram(&[(3, 2 * count), (n, 2)]);
+                (2 * count, 4 * count, 2 * count + 2, degrees, sides)
+            },
+            Seed::Pyramid(n) => {
+                let count = n as usize;
+                let degrees = histogram(&[(3, count), (n, 1)]);
+                let sides = histogram(&[(3, count), (n, 1)]);
+                (count + 1, 2 * count, count + 1, degrees, sides)
+            },
+        };
+        Counts::new(vertices, edges, faces).with_histograms(vertex_degrees, face_sides)
+    }
+}
+
+/// Collects the counts of each degree or number of sides, which may be repeated.
+fn histogram(entries: &[(u32, usize)]) -> Histogram {
+    let mut histogram = Histogram::new();
+    for &(key, count) in entries {
+        *histogram.entry(key).or_insert(0) += count;
+    }
+    histogram
 }
 
+/// Returns the symbol the seed is written with in notation. Prisms, antiprisms and pyramids are
+/// written with their number of sides following the symbol, e.g. `P5`.
 impl From<Seed> for &str {
     fn from(seed: Seed) -> &'static str {
         match seed {
-            Seed::Platonic(platonic) => platonic.into()
+            Seed::Platonic(platonic) => platonic.into(),
+            Seed::Prism(_) => "P",
+            Seed::Antiprism(_) => "A",
+            Seed::Pyramid(_) => "Y",
         }
     }
 }
@@ -28,7 +89,10 @@ impl From<Seed> for &str {
 impl std::fmt::Display for Seed {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Seed::Platonic(platonic) => platonic.fmt(f)
+            Seed::Platonic(platonic) => platonic.fmt(f),
+            Seed::Prism(sides) => write!(f, "Prism ({})", sides),
+            Seed::Antiprism(sides) => write!(f, "Antiprism ({})", sides),
+            Seed::Pyramid(sides) => write!(f, "Pyramid ({})", sides),
         }
     }
 }
@@ -59,6 +59,8 @@ impl Platonic {
                     indices: vec![1, 3, 2],
                 },
             ],
+            chirality: None,
+            seed_faces: (0..4).map(Some).collect(),
         }
     }
 
@@ -96,6 +98,8 @@ impl Platonic {
                     indices: vec![1, 3, 7, 5],
                 },
             ],
+            chirality: None,
+            seed_faces: (0..6).map(Some).collect(),
         }
     }
 
@@ -137,6 +141,8 @@ impl Platonic {
                     indices: vec![1, 3, 4],
                 },
             ],
+            chirality: None,
+            seed_faces: (0..8).map(Some).collect(),
         }
     }
 
@@ -207,6 +213,8 @@ impl Platonic {
                     indices: vec![6, 15, 7, 11, 10],
                 },
             ],
+            chirality: None,
+            seed_faces: (0..12).map(Some).collect(),
         }
     }
 
@@ -291,6 +299,8 @@ impl Platonic {
                     indices: vec![6, 10, 7],
                 },
             ],
+            chirality: None,
+            seed_faces: (0..20).map(Some).collect(),
         }
     }
 }
@@ -0,0 +1,106 @@
+use super::{Face, Polyhedron, Vertex};
+use std::f64::consts::PI;
+
+/// Returns the corners of a regular polygon with the given number of sides, lying in the plane
+/// `z = height` and rotated counterclockwise about the z axis by `turn` sides.
+fn polygon(sides: u32, edge_length: f64, height: f64, turn: f64) -> Vec<Vertex> {
+    let radius = circumradius(sides, edge_length);
+    (0..sides)
+        .map(|i| {
+            let angle = 2.0 * PI * (i as f64 + turn) / sides as f64;
+            [radius * angle.cos(), radius * angle.sin(), height].into()
+        })
+        .collect()
+}
+
+fn circumradius(sides: u32, edge_length: f64) -> f64 {
+    edge_length / (2.0 * (PI / sides as f64).sin())
+}
+
+/// Returns the face of the polygon starting at `first`, facing up if `is_top`, and down otherwise.
+fn polygon_face(sides: u32, first: u32, is_top: bool) -> Face {
+    let mut indices: Vec<u32> = (first..first + sides).collect();
+    if !is_top {
+        indices.reverse();
+    }
+    Face { indices }
+}
+
+pub fn prism(sides: u32, edge_length: f64) -> Polyhedron {
+    assert!(sides >= 3, "Prisms must have at least 3 sides.");
+    let half_height = edge_length / 2.0;
+
+    let mut vertices = polygon(sides, edge_length, -half_height, 0.0);
+    vertices.extend(polygon(sides, edge_length, half_height, 0.0));
+    let mut faces = vec![polygon_face(sides, 0, false), polygon_face(sides, sides, true)];
+    for i in 0..sides {
+        let next = (i + 1) % sides;
+        faces.push(Face {
+            indices: vec![i, next, sides + next, sides + i],
+        });
+    }
+
+    Polyhedron {
+        seed_faces: (0..faces.len() as u32).map(Some).collect(),
+        vertices,
+        faces,
+        chirality: None,
+    }
+}
+
+pub fn antiprism(sides: u32, edge_length: f64) -> Polyhedron {
+    assert!(sides >= 3, "Antiprisms must have at least 3 sides.");
+    // Each lateral edge spans half a side of the polygons around the axis.
+    let chord = 2.0 * circumradius(sides, edge_length) * (PI / (2 * sides) as f64).sin();
+    let half_height = (edge_length * edge_length - chord * chord).sqrt() / 2.0;
+
+    let mut vertices = polygon(sides, edge_length, -half_height, 0.0);
+    vertices.extend(polygon(sides, edge_length, half_height, 0.5));
+    let mut faces = vec![polygon_face(sides, 0, false), polygon_face(sides, sides, true)];
+    for i in 0..sides {
+        let next = (i + 1) % sides;
+        faces.push(Face {
+            indices: vec![i, next, sides + i],
+        });
+        faces.push(Face {
+            indices: vec![sides + i, next, sides + next],
+        });
+    }
+
+    Polyhedron {
+        seed_faces: (0..faces.len() as u32).map(Some).collect(),
+        vertices,
+        faces,
+        chirality: None,
+    }
+}
+
+/// Creates a pyramid whose lateral edges match the edges of its base. Since this is impossible
+/// from 6 sides onwards, larger pyramids are instead as tall as their edges are long.
+pub fn pyramid(sides: u32, edge_length: f64) -> Polyhedron {
+    assert!(sides >= 3, "Pyramids must have at least 3 sides.");
+    let radius = circumradius(sides, edge_length);
+    let height = if sides < 6 {
+        (edge_length * edge_length - radius * radius).sqrt()
+    }
+    else {
+        edge_length
+    };
+
+    // Places the centroid of the pyramid at the origin.
+    let mut vertices = polygon(sides, edge_length, -height / 4.0, 0.0);
+    vertices.push([0.0, 0.0, height * 3.0 / 4.0].into());
+    let mut faces = vec![polygon_face(sides, 0, false)];
+    for i in 0..sides {
+        faces.push(Face {
+            indices: vec![i, (i + 1) % sides, sides],
+        });
+    }
+
+    Polyhedron {
+        seed_faces: (0..faces.len() as u32).map(Some).collect(),
+        vertices,
+        faces,
+        chirality: None,
+    }
+}
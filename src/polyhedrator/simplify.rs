@@ -0,0 +1,233 @@
+//! Shortens sequences of operators using identities between them, such as `dd = identity` or
+//! `dkd = t`. The simplified operators produce a polyhedron with the same structure, though its
+//! vertices may lie in somewhat different positions, since the parameters of the replaced operators
+//! are not carried over.
+
+use super::notation::{print_operators, PrintOptions};
+use super::operators::{Chirality, FaceSelector, Kis, Needle, Operator, Truncate, Zip};
+
+/// The result of simplifying a sequence of operators.
+#[derive(Clone, Debug)]
+pub struct Simplification {
+    operators: Vec<Operator>,
+    rewrites: Vec<Rewrite>,
+}
+
+impl Simplification {
+    /// The simplified operators, in the order they are written.
+    pub fn operators(&self) -> &[Operator] {
+        &self.operators
+    }
+
+    /// Each rewrite made, in the order they were made.
+    pub fn rewrites(&self) -> &[Rewrite] {
+        &self.rewrites
+    }
+
+    pub fn into_operators(self) -> Vec<Operator> {
+        self.operators
+    }
+}
+
+/// A single use of an identity, replacing some consecutive operators with fewer.
+#[derive(Clone, Debug)]
+pub struct Rewrite {
+    identity: &'static str,
+    position: usize,
+    before: Vec<Operator>,
+    after: Vec<Operator>,
+}
+
+impl Rewrite {
+    /// The identity used, such as `dkd = t`.
+    pub fn identity(&self) -> &'static str {
+        self.identity
+    }
+
+    /// The index of the first operator replaced, within the operators as they were before the
+    /// rewrite.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn before(&self) -> &[Operator] {
+        &self.before
+    }
+
+    pub fn after(&self) -> &[Operator] {
+        &self.after
+    }
+}
+
+impl std::fmt::Display for Rewrite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let after = match print_operators(&self.after, PrintOptions::default()) {
+            after if after.is_empty() => "nothing".to_string(),
+            after => after,
+        };
+        write!(
+            f,
+            "{} -> {} at position {}, since {}",
+            print_operators(&self.before, PrintOptions::default()),
+            after,
+            self.position,
+            self.identity
+        )
+    }
+}
+
+/// Simplifies operators written in the usual order, so that the last operator is applied first.
+/// Identities are applied from the left until none remain, and each one shortens the sequence.
+pub fn simplify(operators: &[Operator]) -> Simplification {
+    let mut operators = operators.to_vec();
+    let mut rewrites = Vec::new();
+    let mut position = 0;
+    while position < operators.len() {
+        let terms: Vec<Option<Term>> =
+            operators[position..].iter().take(3).map(Term::from).collect();
+        match rewrite(&terms) {
+            Some((identity, length, replacement)) => {
+                let after: Vec<Operator> = replacement.into_iter().map(Operator::from).collect();
+                let before = operators
+                    .splice(position..position + length, after.iter().cloned())
+                    .collect();
+                rewrites.push(Rewrite {
+                    identity,
+                    position,
+                    before,
+                    after,
+                });
+                // The replacement may complete an identity with the operators before it.
+                position = position.saturating_sub(2);
+            },
+            None => position += 1,
+        }
+    }
+
+    Simplification {
+        operators,
+        rewrites,
+    }
+}
+
+/// An operator that takes part in identities. Operators with other parameters, such as a kis
+/// with a selector beyond the side count or an apex height, do not.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Term {
+    Ambo,
+    Dual,
+    Join,
+    Ortho,
+    Expand,
+    Bevel,
+    Meta,
+    Reflect,
+    Gyro(Chirality),
+    Snub(Chirality),
+    Kis(u32),
+    Truncate(u32),
+    Needle(u32),
+    Zip(u32),
+}
+
+impl Term {
+    fn from(operator: &Operator) -> Option<Term> {
+        let only_sides = |selector: &FaceSelector| {
+            *selector == FaceSelector::default().with_sides(selector.side_count())
+        };
+        let term = match operator {
+            Operator::Ambo => Term::Ambo,
+            Operator::Dual => Term::Dual,
+            Operator::Join => Term::Join,
+            Operator::Ortho => Term::Ortho,
+            Operator::Expand => Term::Expand,
+            Operator::Bevel => Term::Bevel,
+            Operator::Meta => Term::Meta,
+            Operator::Reflect => Term::Reflect,
+            Operator::Gyro(chirality) => Term::Gyro(*chirality),
+            Operator::Snub(chirality) => Term::Snub(*chirality),
+            Operator::Kis(kis) if kis.apex_scale() == 0.0 && only_sides(kis.selector()) => {
+                Term::Kis(kis.side_count())
+            },
+            Operator::Truncate(truncate) if truncate.depth() == Truncate::default().depth() => {
+                Term::Truncate(truncate.degree())
+            },
+            Operator::Needle(needle) => Term::Needle(needle.side_count()),
+            Operator::Zip(zip) if only_sides(zip.selector()) => Term::Zip(zip.side_count()),
+            _ => return None,
+        };
+        Some(term)
+    }
+}
+
+impl From<Term> for Operator {
+    fn from(term: Term) -> Self {
+        match term {
+            Term::Ambo => Operator::Ambo,
+            Term::Dual => Operator::Dual,
+            Term::Join => Operator::Join,
+            Term::Ortho => Operator::Ortho,
+            Term::Expand => Operator::Expand,
+            Term::Bevel => Operator::Bevel,
+            Term::Meta => Operator::Meta,
+            Term::Reflect => Operator::Reflect,
+            Term::Gyro(chirality) => Operator::Gyro(chirality),
+            Term::Snub(chirality) => Operator::Snub(chirality),
+            Term::Kis(sides) => Operator::Kis(Kis::restrict_to_sides_and_scale_apex(sides, 0.0)),
+            Term::Truncate(degree) => Operator::Truncate(Truncate::restrict_to_degree(degree)),
+            Term::Needle(sides) => Operator::Needle(Needle::restrict_to_sides(sides)),
+            Term::Zip(sides) => Operator::Zip(Zip::restrict_to_sides(sides)),
+        }
+    }
+}
+
+/// Finds an identity that applies to the start of the terms, returning it along with the number
+/// of terms it replaces and their replacement.
+fn rewrite(terms: &[Option<Term>]) -> Option<(&'static str, usize, Vec<Term>)> {
+    use Term::*;
+
+    let rewrite = match terms {
+        [Some(Dual), Some(Kis(sides)), Some(Dual), ..] => ("dkd = t", 3, vec![Truncate(*sides)]),
+        [Some(Dual), Some(Truncate(degree)), Some(Dual), ..] => {
+            ("dtd = k", 3, vec![Kis(*degree)])
+        },
+        [Some(Dual), Some(Dual), ..] => ("dd = identity", 2, vec![]),
+        [Some(Reflect), Some(Reflect), ..] => ("rr = identity", 2, vec![]),
+        [Some(Ambo), Some(Ambo), ..] => ("aa = e", 2, vec![Expand]),
+        [Some(Join), Some(Join), ..] => ("jj = o", 2, vec![Ortho]),
+        [Some(Truncate(0)), Some(Ambo), ..] => ("ta = b", 2, vec![Bevel]),
+        [Some(Kis(0)), Some(Join), ..] => ("kj = m", 2, vec![Meta]),
+
+        // Operators which give the same result for a polyhedron and its dual.
+        [Some(Ambo), Some(Dual), ..] => ("ad = a", 2, vec![Ambo]),
+        [Some(Join), Some(Dual), ..] => ("jd = j", 2, vec![Join]),
+        [Some(Expand), Some(Dual), ..] => ("ed = e", 2, vec![Expand]),
+        [Some(Ortho), Some(Dual), ..] => ("od = o", 2, vec![Ortho]),
+        [Some(Bevel), Some(Dual), ..] => ("bd = b", 2, vec![Bevel]),
+        [Some(Meta), Some(Dual), ..] => ("md = m", 2, vec![Meta]),
+        [Some(Gyro(chirality)), Some(Dual), ..] => ("gd = g", 2, vec![Gyro(*chirality)]),
+        [Some(Snub(chirality)), Some(Dual), ..] => ("sd = s", 2, vec![Snub(*chirality)]),
+
+        // Pairs of operators which are each other's duals.
+        [Some(Dual), Some(Ambo), ..] => ("da = j", 2, vec![Join]),
+        [Some(Dual), Some(Join), ..] => ("dj = a", 2, vec![Ambo]),
+        [Some(Dual), Some(Expand), ..] => ("de = o", 2, vec![Ortho]),
+        [Some(Dual), Some(Ortho), ..] => ("do = e", 2, vec![Expand]),
+        [Some(Dual), Some(Bevel), ..] => ("db = m", 2, vec![Meta]),
+        [Some(Dual), Some(Meta), ..] => ("dm = b", 2, vec![Bevel]),
+        [Some(Dual), Some(Gyro(chirality)), ..] => ("dg = s", 2, vec![Snub(*chirality)]),
+        [Some(Dual), Some(Snub(chirality)), ..] => ("ds = g", 2, vec![Gyro(*chirality)]),
+
+        // Kis, truncate, needle and zip are related through the dual.
+        [Some(Dual), Some(Kis(sides)), ..] => ("dk = z", 2, vec![Zip(*sides)]),
+        [Some(Kis(sides)), Some(Dual), ..] => ("kd = n", 2, vec![Needle(*sides)]),
+        [Some(Dual), Some(Zip(sides)), ..] => ("dz = k", 2, vec![Kis(*sides)]),
+        [Some(Zip(sides)), Some(Dual), ..] => ("zd = t", 2, vec![Truncate(*sides)]),
+        [Some(Dual), Some(Needle(sides)), ..] => ("dn = t", 2, vec![Truncate(*sides)]),
+        [Some(Needle(sides)), Some(Dual), ..] => ("nd = k", 2, vec![Kis(*sides)]),
+        [Some(Dual), Some(Truncate(degree)), ..] => ("dt = n", 2, vec![Needle(*degree)]),
+        [Some(Truncate(degree)), Some(Dual), ..] => ("td = z", 2, vec![Zip(*degree)]),
+        _ => return None,
+    };
+    Some(rewrite)
+}
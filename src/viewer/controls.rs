@@ -1,13 +1,15 @@
 use iced_wgpu::Renderer;
-use iced_winit::{button, text_input, Align, Button, Column, Element, Length, Radio, Row, Text, TextInput};
+use iced_winit::{button, text_input, Align, Button, Checkbox, Column, Element, Length, Radio, Row, Text, TextInput};
 use crate::seeds::{Seed, Platonic};
-use crate::{operators, Operator};
+use crate::{operators, BuildOptions, Notation, Operator};
 use super::generator::Generator;
 
 pub struct Controls {
-    seed: Seed,
-    operations: Vec<Operator>,
+    notation: Notation,
+    notation_text: String,
+    notation_error: Option<String>,
     notation_input: text_input::State,
+    simplify: bool,
     update_button: button::State,
 }
 
@@ -15,6 +17,7 @@ pub struct Controls {
 pub enum Message {
     SeedSelected(Seed),
     NotationChanged(String),
+    SimplifyToggled(bool),
     UpdatePressed,
 }
 
@@ -23,61 +26,84 @@ impl Controls {
         let kis = operators::Kis::scale_apex(0.0);
         let operations = vec![
             Operator::Dual,
-            Operator::Kis(kis),
+            Operator::Kis(kis.clone()),
             Operator::Dual,
-            Operator::Kis(kis),
+            Operator::Kis(kis.clone()),
             Operator::Dual,
-            Operator::Kis(kis),
+            Operator::Kis(kis.clone()),
             Operator::Dual,
             Operator::Kis(kis),
         ];
+        let notation = Notation::new(Seed::Platonic(Platonic::Dodecahedron), operations);
         Controls {
-            seed: Seed::Platonic(Platonic::Dodecahedron),
-            operations,
+            notation_text: notation.to_string(),
+            notation,
+            notation_error: None,
             notation_input: text_input::State::focused(),
+            simplify: false,
             update_button: Default::default(),
         }
     }
 
     pub fn update(&mut self, message: Message, state: &mut super::State, device: &wgpu::Device) {
         match message {
-            Message::SeedSelected(seed) => self.seed = seed,
+            Message::SeedSelected(seed) => {
+                self.notation = Notation::new(seed, self.notation.operators().to_vec());
+                self.notation_text = self.notation.to_string();
+                self.notation_error = None;
+            },
             Message::UpdatePressed => {
-                let mut generator = Generator::seed(self.seed.polyhedron(2.0));
-                generator.apply_iter(self.operations.iter().rev().cloned());
-                generator.scale(2.0);
-                let update = super::render::Update {
-                    mesh: Some(generator.to_mesh()), .. Default::default()
-                };
-                state.apply_update(device, update);
+                let options = BuildOptions::default().simplify(self.simplify);
+                match self.notation.build_with(options) {
+                    Ok(polyhedron) => {
+                        let mut generator = Generator::seed(polyhedron);
+                        generator.scale(2.0);
+                        let update = super::render::Update {
+                            mesh: Some(generator.to_mesh()), .. Default::default()
+                        };
+                        state.apply_update(device, update);
+                    },
+                    Err(error) => self.notation_error = Some(error.to_string()),
+                }
             },
             Message::NotationChanged(notation) => {
-                if let Ok(operations) = Operator::try_parse(&notation) {
-                    self.operations = operations;
+                match notation.parse() {
+                    Ok(notation) => {
+                        self.notation = notation;
+                        self.notation_error = None;
+                    },
+                    Err(error) => self.notation_error = Some(error.to_string()),
                 }
+                self.notation_text = notation;
             },
+            Message::SimplifyToggled(simplify) => self.simplify = simplify,
         }
     }
 
     pub fn view(&mut self) -> Element<Message, Renderer> {
         let mut seed_column = Column::new().width(Length::Units(170)).spacing(10)
             .push(Text::new("Seed"));
-        for seed in Platonic::all().iter().cloned().map(Seed::Platonic) {
-            let radio = Radio::new(seed, &seed.to_string(), Some(self.seed), Message::SeedSelected);
+        // Prisms, antiprisms and pyramids keep the number of sides of the current seed
+        let sides = match self.notation.seed() {
+            Seed::Prism(sides) | Seed::Antiprism(sides) | Seed::Pyramid(sides) => sides,
+            Seed::Platonic(_) => 5,
+        };
+        let polygonal = vec![Seed::Prism(sides), Seed::Antiprism(sides), Seed::Pyramid(sides)];
+        for seed in Platonic::all().iter().cloned().map(Seed::Platonic).chain(polygonal) {
+            let radio = Radio::new(seed, &seed.to_string(), Some(self.notation.seed()), Message::SeedSelected);
             seed_column = seed_column.push(radio);
         }
 
-        let notation_text = self.operations.iter().fold(String::with_capacity(self.operations.len()), |mut notation, op| -> String {
-            let str: String = (*op).into();
-            notation.push_str(&str);
-            notation
-        });
-
-        let notation_element = TextInput::new(&mut self.notation_input, "e.g. dkdkdk", &notation_text, Message::NotationChanged);
+        let notation_element = TextInput::new(&mut self.notation_input, "e.g. dkdkdkI", &self.notation_text, Message::NotationChanged);
 
         seed_column = seed_column
-            .push(Text::new("Operations"))
-            .push(notation_element)
+            .push(Text::new("Notation"))
+            .push(notation_element);
+        if let Some(error) = &self.notation_error {
+            seed_column = seed_column.push(Text::new(error));
+        }
+        seed_column = seed_column
+            .push(Checkbox::new(self.simplify, "Simplify", Message::SimplifyToggled))
             .push(Button::new(&mut self.update_button, Text::new("Update"))
                 .on_press(Message::UpdatePressed));
 
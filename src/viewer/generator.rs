@@ -1,5 +1,5 @@
 use super::render;
-use crate::{Operator, Polyhedron};
+use crate::Polyhedron;
 
 pub struct Generator {
     polyhedron: Polyhedron,
@@ -12,22 +12,6 @@ impl Generator {
         }
     }
 
-    pub fn apply_operator(&mut self, operator: Operator) {
-        let temp_value = crate::seeds::Platonic::Tetrahedron.polyhedron(1.0);
-        let old = std::mem::replace(&mut self.polyhedron, temp_value);
-        let new = old.apply(operator);
-        std::mem::replace(&mut self.polyhedron, new);
-    }
-
-    pub fn apply_iter(&mut self, operators: impl IntoIterator<Item = Operator>) {
-        let temp_value = crate::seeds::Platonic::Tetrahedron.polyhedron(1.0);
-        let mut polyhedron = std::mem::replace(&mut self.polyhedron, temp_value);
-        for op in operators.into_iter() {
-            polyhedron = polyhedron.apply(op);
-        }
-        std::mem::replace(&mut self.polyhedron, polyhedron);
-    }
-
     pub fn scale(&mut self, max_radius: f64) {
         self.polyhedron.center_on_origin();
         self.polyhedron.scale(max_radius);
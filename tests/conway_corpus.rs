@@ -0,0 +1,74 @@
+//! Checks notation copied from polyHédronisme and Antiprism against the sizes of the polyhedra
+//! those programs produce.
+
+use polyhedrator::{NotationContext, Syntax};
+
+/// Notation in the syntax of another program, and the vertex, edge and face counts it produces.
+const CORPUS: &[(Syntax, &str, usize, usize, usize)] = &[
+    (Syntax::PolyHedronisme, "T", 4, 6, 4),
+    (Syntax::PolyHedronisme, "P5", 10, 15, 7),
+    (Syntax::PolyHedronisme, "A7", 14, 28, 16),
+    (Syntax::PolyHedronisme, "Y4", 5, 8, 5),
+    (Syntax::PolyHedronisme, "dP5", 7, 15, 10),
+    (Syntax::PolyHedronisme, "tT", 12, 18, 8),
+    (Syntax::PolyHedronisme, "tO", 24, 36, 14),
+    (Syntax::PolyHedronisme, "tI", 60, 90, 32),
+    (Syntax::PolyHedronisme, "aC", 12, 24, 14),
+    (Syntax::PolyHedronisme, "jC", 14, 24, 12),
+    (Syntax::PolyHedronisme, "oC", 26, 48, 24),
+    (Syntax::PolyHedronisme, "mC", 26, 72, 48),
+    (Syntax::PolyHedronisme, "eD", 60, 120, 62),
+    (Syntax::PolyHedronisme, "bC", 48, 72, 26),
+    (Syntax::PolyHedronisme, "gC", 38, 60, 24),
+    (Syntax::PolyHedronisme, "sC", 24, 60, 38),
+    (Syntax::PolyHedronisme, "sD", 60, 150, 92),
+    (Syntax::PolyHedronisme, "pC", 32, 60, 30),
+    (Syntax::PolyHedronisme, "cC", 32, 48, 18),
+    (Syntax::PolyHedronisme, "wC", 56, 84, 30),
+    (Syntax::PolyHedronisme, "k5tI", 72, 150, 80),
+    (Syntax::PolyHedronisme, "t4kC", 32, 60, 30),
+    (Syntax::PolyHedronisme, "k(5)tI", 72, 150, 80),
+    (Syntax::PolyHedronisme, "u2I", 42, 120, 80),
+    (Syntax::PolyHedronisme, "n5D", 80, 150, 72),
+    (Syntax::PolyHedronisme, "x4C", 32, 60, 30),
+    (Syntax::PolyHedronisme, "dakD", 92, 180, 90),
+    (Syntax::Antiprism, "nC", 14, 36, 24),
+    (Syntax::Antiprism, "zD", 60, 90, 32),
+    (Syntax::Antiprism, "LC", 32, 84, 54),
+    (Syntax::Antiprism, "L0C", 32, 72, 42),
+    (Syntax::Antiprism, "KC", 38, 84, 48),
+    (Syntax::Antiprism, "lC", 32, 60, 30),
+    (Syntax::Antiprism, "XC", 50, 120, 72),
+    (Syntax::Antiprism, "qC", 44, 72, 30),
+    (Syntax::Antiprism, "t3kO", 30, 60, 32),
+];
+
+#[test]
+fn corpus_sizes() {
+    for &(syntax, notation, vertices, edges, faces) in CORPUS {
+        let mut context = NotationContext::new();
+        context.set_syntax(syntax);
+        let polyhedron = context.parse_notation(notation).unwrap().build();
+        let sides: usize = polyhedron.faces().iter().map(|face| face.indices().len()).sum();
+        let sizes = (polyhedron.vertices().len(), sides / 2, polyhedron.faces().len());
+        assert_eq!(sizes, (vertices, edges, faces), "{} in {}", notation, syntax);
+    }
+}
+
+#[test]
+fn unsupported_symbols() {
+    let cases = [
+        (Syntax::PolyHedronisme, "HC", "hollow (`H`) from polyHédronisme notation is not supported"),
+        (Syntax::PolyHedronisme, "kJ37", "Johnson solid (`J`) seeds from polyHédronisme notation"),
+        (Syntax::PolyHedronisme, "Kk5D", "`K` is not an operator in polyHédronisme notation"),
+        (Syntax::PolyHedronisme, "k(5,0.2)D", "decimal parameters from polyHédronisme notation"),
+        (Syntax::Antiprism, "M0C", "joined-medial (`M0`) from Antiprism notation"),
+        (Syntax::Antiprism, "xC", "`x` is not an operator in Antiprism notation"),
+    ];
+    for &(syntax, notation, message) in &cases {
+        let mut context = NotationContext::new();
+        context.set_syntax(syntax);
+        let error = context.parse_notation(notation).unwrap_err();
+        assert!(error.to_string().starts_with(message), "{}: {}", notation, error);
+    }
+}
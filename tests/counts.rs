@@ -0,0 +1,95 @@
+//! Checks the counts predicted for each operator against those of the polyhedra it builds, and
+//! how the predictions limit which notation is built.
+
+use polyhedrator::{BuildOptions, Counts, Notation, SizeLimit};
+
+/// Operators of every kind, including those acting on only some faces, vertices or edges.
+const OPERATORS: &[&str] = &[
+    "a", "d", "k", "k4", "t", "t3", "j", "o", "e", "g", "g'", "s", "s'", "b", "m", "n", "n5", "z",
+    "z4", "p", "p'", "r", "c", "c(0.3)", "w", "w'", "l", "l4", "L", "L4", "K", "K4", "q", "q4",
+    "L0", "L04", "x", "x4", "u3", "c(2,1)", "i", "i4", "E(0.2)", "E4(0.2,0.5)", "k{class=0}",
+    "k{area=1..}", "c{3,3}", "c{0,4}",
+];
+
+/// Seeds with faces of several shapes, and vertices of several degrees once `k` is applied.
+const SEEDS: &[&str] = &["T", "C", "D", "P5", "A4", "Y6", "kC", "aD"];
+
+/// Checks the predicted counts against those built. Exact predictions give the same counts, and
+/// every histogram they know, while others are at least as large.
+fn check(notation: &Notation) {
+    let predicted = notation.counts();
+    let built = Counts::of(&notation.build());
+    let sizes = |counts: &Counts| (counts.vertices(), counts.edges(), counts.faces());
+    if predicted.is_exact() {
+        assert_eq!(sizes(&predicted), sizes(&built), "sizes of {}", notation);
+        if let Some(vertex_degrees) = predicted.vertex_degrees() {
+            assert_eq!(Some(vertex_degrees), built.vertex_degrees(), "degrees of {}", notation);
+        }
+        if let Some(face_sides) = predicted.face_sides() {
+            assert_eq!(Some(face_sides), built.face_sides(), "face sides of {}", notation);
+        }
+    } else {
+        assert!(predicted.vertices() >= built.vertices(), "vertices of {}", notation);
+        assert!(predicted.edges() >= built.edges(), "edges of {}", notation);
+        assert!(predicted.faces() >= built.faces(), "faces of {}", notation);
+    }
+}
+
+#[test]
+fn predicted_counts() {
+    for operator in OPERATORS {
+        for seed in SEEDS {
+            check(&format!("{}{}", operator, seed).parse().unwrap());
+        }
+    }
+}
+
+#[test]
+fn predicted_counts_of_sequences() {
+    for value in &["dkdkdkdkP5", "tktkC", "jkgA5", "u2c(2,1)T", "i4lqY4", "xLKwD"] {
+        let notation: Notation = value.parse().unwrap();
+        assert!(notation.counts().is_exact(), "{}", value);
+        check(&notation);
+    }
+}
+
+#[test]
+fn limit() {
+    let notation: Notation = "(dk)^10D".parse().unwrap();
+    let error = notation.build_with(BuildOptions::default()).unwrap_err();
+    assert_eq!(error.limit(), Some(SizeLimit::DEFAULT_EDGES));
+    assert_eq!(error.counts(), &notation.counts());
+    assert!(error.counts().edges() > SizeLimit::DEFAULT_EDGES);
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "the polyhedron would have {} edges, more than the limit of 1000000",
+            error.counts().edges()
+        )
+    );
+
+    let notation: Notation = "dkD".parse().unwrap();
+    let options = BuildOptions::default().limit(SizeLimit::edges(89));
+    let error = notation.build_with(options).unwrap_err();
+    assert_eq!(error.limit(), Some(89));
+    assert_eq!(error.counts().edges(), 90);
+    let options = BuildOptions::default().limit(SizeLimit::edges(90));
+    assert_eq!(Counts::of(&notation.build_with(options).unwrap()).edges(), 90);
+}
+
+#[test]
+fn overflow() {
+    for value in &["(g)^40C", "u4294967295I", "(dk)^50D", "x(g)^40C"] {
+        let notation: Notation = value.parse().unwrap();
+        let counts = notation.counts();
+        assert!(counts.is_overflowed(), "{}", value);
+        assert!(!counts.is_exact(), "{}", value);
+
+        let options = BuildOptions::default().limit(SizeLimit::unlimited());
+        let error = notation.build_with(options).unwrap_err();
+        assert_eq!(error.limit(), None, "{}", value);
+        assert_eq!(error.to_string(), "the polyhedron would have too many edges to count");
+        let error = notation.build_with(BuildOptions::default()).unwrap_err();
+        assert_eq!(error.limit(), Some(SizeLimit::DEFAULT_EDGES), "{}", value);
+    }
+}
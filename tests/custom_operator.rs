@@ -0,0 +1,194 @@
+//! Checks that operators defined outside the library can be built and written in notation.
+
+use polyhedrator::notation::Rule;
+use polyhedrator::{
+    center, mean_distance, normal, Builder, ConwayOperator, Counts, FaceKey, FaceSelector,
+    NotationContext, Polyhedron, VertexKey,
+};
+
+/// Copies each vertex and face of the polyhedron unchanged.
+#[derive(Debug)]
+struct Identity;
+
+impl ConwayOperator for Identity {
+    fn apply(&self, polyhedron: &Polyhedron, builder: &mut Builder) {
+        for (i, vertex) in polyhedron.vertices().iter().enumerate() {
+            builder.add_vertex(VertexKey::Seed(i as u32), *vertex);
+        }
+        for (i, face) in polyhedron.faces().iter().enumerate() {
+            let mut previous = *face.indices().last().unwrap();
+            for &index in face.indices() {
+                let (source, destination) = (VertexKey::Seed(previous), VertexKey::Seed(index));
+                builder.add_flag(FaceKey::Seed(i as u32), source, destination);
+                previous = index;
+            }
+        }
+    }
+
+    fn transform_counts(&self, counts: &Counts) -> Counts {
+        counts.clone()
+    }
+}
+
+/// Ambo as it would be written outside the library, joining the midpoints of each edge.
+#[derive(Debug)]
+struct Rectify;
+
+impl ConwayOperator for Rectify {
+    fn apply(&self, polyhedron: &Polyhedron, builder: &mut Builder) {
+        let vertices = polyhedron.vertices();
+        for (i, face) in polyhedron.faces().iter().enumerate() {
+            let (mut v1, mut v2) = face.last();
+            for &v3 in face.indices() {
+                let (first, second) = (vertices[v1 as usize], vertices[v2 as usize]);
+                builder.add_vertex(VertexKey::midpoint(v1, v2), first + (second - first) / 2.0);
+
+                let incoming = VertexKey::midpoint(v1, v2);
+                let outgoing = VertexKey::midpoint(v2, v3);
+                builder.add_flag(FaceKey::Seed(i as u32), incoming, outgoing);
+                builder.add_flag(FaceKey::Vertex(v2), outgoing, incoming);
+                v1 = v2;
+                v2 = v3;
+            }
+        }
+    }
+
+    fn transform_counts(&self, counts: &Counts) -> Counts {
+        counts.ambo()
+    }
+}
+
+/// Raises a pyramid on each face, with its apex as far above the center of the face as the
+/// vertices of the face are from its center.
+#[derive(Debug)]
+struct Pyramids;
+
+impl ConwayOperator for Pyramids {
+    fn apply(&self, polyhedron: &Polyhedron, builder: &mut Builder) {
+        for (i, vertex) in polyhedron.vertices().iter().enumerate() {
+            builder.add_vertex(VertexKey::Seed(i as u32), *vertex);
+        }
+        for (i, face) in polyhedron.faces().iter().enumerate() {
+            let vertices = polyhedron.face_vertices(face);
+            let center = center(vertices.clone());
+            let height = mean_distance(vertices.clone(), center);
+            let apex = VertexKey::Centroid(i as u32);
+            builder.add_vertex(apex, center + normal(vertices) * height);
+
+            let mut previous = VertexKey::Seed(*face.indices().last().unwrap());
+            for &index in face.indices() {
+                let current = VertexKey::Seed(index);
+                let key = FaceKey::PyramidFace(i as u32, previous);
+                builder.add_flag(key, previous, current);
+                builder.add_flag(key, current, apex);
+                builder.add_flag(key, apex, previous);
+                previous = current;
+            }
+        }
+    }
+
+    fn transform_counts(&self, counts: &Counts) -> Counts {
+        counts.kis(&FaceSelector::default())
+    }
+}
+
+/// Returns the vertices of the polyhedron in order, rounded so that they compare equal despite
+/// small differences in how they were calculated.
+fn rounded_vertices(polyhedron: &Polyhedron) -> Vec<[i64; 3]> {
+    let round = |value: f64| (value * 1e9).round() as i64;
+    let mut vertices: Vec<_> =
+        polyhedron.vertices().iter().map(|v| [round(v.x), round(v.y), round(v.z)]).collect();
+    vertices.sort();
+    vertices
+}
+
+fn sizes(polyhedron: &Polyhedron) -> (usize, usize, usize) {
+    let sides: usize = polyhedron.faces().iter().map(|face| face.indices().len()).sum();
+    (polyhedron.vertices().len(), sides / 2, polyhedron.faces().len())
+}
+
+#[test]
+fn registered_operators() {
+    let mut context = NotationContext::new();
+    context.register('H', Identity).unwrap();
+
+    let notation = context.parse_notation("HkHI").unwrap();
+    assert_eq!(notation.to_string(), "HkHI");
+    assert_eq!(context.parse_notation("H kis H icosahedron").unwrap(), notation);
+    assert_eq!(context.parse_notation("#m := Hk; (#m)H[]I").unwrap(), notation);
+    context.define("m", "(H)k").unwrap();
+    assert_eq!(context.parse_notation("(#m)HI").unwrap(), notation);
+
+    let polyhedron = notation.build();
+    let expected = context.parse_notation("kI").unwrap().build();
+    assert_eq!(sizes(&polyhedron), sizes(&expected));
+    assert_eq!(notation.counts(), Counts::of(&polyhedron));
+}
+
+#[test]
+fn reimplemented_operators() {
+    let mut context = NotationContext::new();
+    context.register('R', Rectify).unwrap();
+    context.register('Q', Pyramids).unwrap();
+
+    let cases = [("RC", "aC"), ("RRD", "aaD"), ("RkT", "akT"), ("QC", "k(1)C"), ("QRD", "k(1)aD")];
+    for (custom, built_in) in cases.iter() {
+        let notation = context.parse_notation(custom).unwrap();
+        let expected = context.parse_notation(built_in).unwrap();
+        let polyhedron = notation.build();
+        let expected_polyhedron = expected.build();
+        assert_eq!(notation.counts(), expected.counts(), "{}", custom);
+        assert_eq!(Counts::of(&polyhedron), Counts::of(&expected_polyhedron), "{}", custom);
+        assert_eq!(notation.counts(), Counts::of(&polyhedron), "{}", custom);
+        assert_eq!(rounded_vertices(&polyhedron), rounded_vertices(&expected_polyhedron));
+    }
+}
+
+#[test]
+fn built_without_notation() {
+    // A builder may be used directly, without an operator
+    let seed = "C".parse::<polyhedrator::Notation>().unwrap().build();
+    let mut builder = Builder::new();
+    Rectify.apply(&seed, &mut builder);
+    let polyhedron = builder.build_polyhedron(seed.chirality(), seed.seed_faces());
+    assert_eq!(sizes(&polyhedron), (12, 24, 14));
+    assert_eq!(polyhedron.seed_faces().iter().filter(|face| face.is_some()).count(), 6);
+
+    // Face normals point away from the center of a convex polyhedron
+    for face in seed.faces() {
+        let vertices = seed.face_vertices(face);
+        let outward = center(vertices.clone()) - cgmath::Point3::new(0.0, 0.0, 0.0);
+        assert!(cgmath::InnerSpace::dot(normal(vertices), outward) > 0.0);
+    }
+}
+
+#[test]
+fn unregistered_operators() {
+    // Without registered operators, other letters are syntax errors, which never suggest one
+    let context = NotationContext::new();
+    for value in &["HkI", "kfI", "dkqx3"] {
+        let error = context.parse_notation(value).unwrap_err();
+        assert!(error.to_string().starts_with("unexpected"), "{}", error);
+        assert!(!error.expected().contains(&Rule::custom_operator), "{}", error);
+        assert!(!error.to_string().contains("registered"), "{}", error);
+    }
+
+    let mut context = NotationContext::new();
+    context.register('H', Identity).unwrap();
+    let error = context.parse_notation("HkfI").unwrap_err();
+    assert_eq!(error.to_string(), "operator is not registered at position 2");
+    let error = context.parse_notation("dkqx3").unwrap_err();
+    assert!(error.expected().contains(&Rule::custom_operator), "{}", error);
+}
+
+#[test]
+fn reserved_symbols() {
+    let mut context = NotationContext::new();
+    let error = context.register('k', Identity).unwrap_err();
+    assert_eq!(error.to_string(), "symbol `k` is used by a built-in operator or seed");
+    let error = context.register('C', Identity).unwrap_err();
+    assert_eq!(error.to_string(), "symbol `C` is used by a built-in operator or seed");
+    let error = context.register('1', Identity).unwrap_err();
+    assert_eq!(error.to_string(), "symbol `1` is not an ASCII letter");
+    assert!(context.registered('k').is_none());
+}
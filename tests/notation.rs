@@ -0,0 +1,110 @@
+//! Checks that notation reads back the same after it is printed, and how notations compare.
+
+use polyhedrator::{Dialect, Notation, PrintOptions};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Notation using every operator, with and without parameters, and every kind of seed.
+const NOTATIONS: &[&str] = &[
+    "I",
+    "dkdkdkdkP5",
+    "adkk5(0.2)tt3(0.25)A7",
+    "joegs'g'bmY4",
+    "n5z{sides=4,class=1}pp'rC",
+    "cc{5,6}(0.3)ww'(0.5)D",
+    "lLKqL0x4kkkO",
+    "uu3c(2,1)T",
+    "ii5(0.3,-0.1)EE4(0.2,0.5)C",
+    "k{area=0.5..2,normal=[0,0,1]<30,index=[0,2],seed=[1]}D",
+    "dadadaddtkI",
+];
+
+fn hash(notation: &Notation) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    notation.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn check_round_trip(options: PrintOptions) {
+    for value in NOTATIONS {
+        let notation: Notation = value.parse().unwrap();
+        let printed = notation.print(options);
+        let reparsed: Notation = printed.parse().unwrap();
+        assert_eq!(reparsed, notation, "{} printed as {}", value, printed);
+        assert_eq!(hash(&reparsed), hash(&notation), "{} printed as {}", value, printed);
+    }
+}
+
+#[test]
+fn compact_round_trip() {
+    check_round_trip(PrintOptions::default());
+    for value in NOTATIONS {
+        assert_eq!(value.parse::<Notation>().unwrap().to_string(), *value);
+    }
+}
+
+#[test]
+fn verbose_round_trip() {
+    check_round_trip(PrintOptions::default().dialect(Dialect::Verbose));
+}
+
+#[test]
+fn compressed_round_trip() {
+    check_round_trip(PrintOptions::default().compress(true));
+    check_round_trip(PrintOptions::default().compress(true).dialect(Dialect::Verbose));
+
+    let notation: Notation = "dkdkdkdkP5".parse().unwrap();
+    assert_eq!(notation.print(PrintOptions::default().compress(true)), "(dk)^4P5");
+}
+
+#[test]
+fn equality() {
+    let parse = |value: &str| value.parse::<Notation>().unwrap();
+    let pairs = [
+        ("k(0.50)C", "k(0.5)C"),
+        ("k(-0)C", "kC"),
+        ("i(d=-0.0)C", "iC"),
+        ("k{seed=[2,1,1]}D", "k{seed=[1,2]}D"),
+        ("dual kis(5) ambo icosahedron", "dk5aI"),
+    ];
+    for (first, second) in pairs.iter() {
+        assert_eq!(parse(first), parse(second), "{} and {}", first, second);
+        assert_eq!(hash(&parse(first)), hash(&parse(second)), "{} and {}", first, second);
+    }
+    assert_ne!(parse("k(0.2)C"), parse("k(0.3)C"));
+    assert_ne!(parse("sC"), parse("s'C"));
+    assert_ne!(parse("dkC"), parse("kdC"));
+}
+
+#[test]
+fn invalid_decimals() {
+    let ones = "1".repeat(400);
+    let nines = "9".repeat(400);
+    let zeros = "0".repeat(400);
+    // Nonzero, but too short for its length to be measured
+    let tiny = format!("0.{}1", "0".repeat(200));
+    let cases = [
+        (format!("k({})I", ones), "decimal is too large", 2),
+        (format!("k{{area=0..{}}}I", nines), "decimal is too large", 10),
+        (format!("i(0.5,{})C", nines), "decimal is too large", 6),
+        (format!("k(h={})I", nines), "decimal is too large", 4),
+        (format!("k{{normal=[1{},0,0]<10}}C", zeros), "decimal is too large", 10),
+        (format!("k{{normal=[0.{}1,0,0]<10}}C", zeros), "normal direction must not be zero", 2),
+        (format!("k{{normal=[{0},{0},0]<10}}C", tiny), "normal direction must not be zero", 2),
+        ("k{normal=[0,0,0]<10}C".to_string(), "normal direction must not be zero", 2),
+    ];
+    for (value, message, position) in cases.iter() {
+        let error = value.parse::<Notation>().unwrap_err();
+        let expected = format!("{} at position {}", message, position);
+        assert_eq!(error.to_string(), expected, "{}", value);
+    }
+}
+
+#[test]
+fn invalid_goldberg_coxeter() {
+    for value in &["c(0,0)C", "u0C", "subdivide(0) cube", "goldberg-coxeter(0, 0) cube"] {
+        let error = value.parse::<Notation>().unwrap_err();
+        let expected = "Goldberg-Coxeter parameters must not both be zero at position 0";
+        assert_eq!(error.to_string(), expected, "{}", value);
+    }
+}
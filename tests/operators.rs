@@ -0,0 +1,123 @@
+//! Checks the sizes of the polyhedra each operator builds, and how many sides their faces have.
+
+use polyhedrator::{Chirality, Notation};
+use std::collections::BTreeMap;
+
+/// Builds the notation, and checks its vertex, edge and face counts along with how many faces
+/// have each number of sides.
+fn check(notation: &str, sizes: (usize, usize, usize), face_sides: &[(usize, usize)]) {
+    let polyhedron = notation.parse::<Notation>().unwrap().build();
+    let mut histogram = BTreeMap::new();
+    for face in polyhedron.faces() {
+        *histogram.entry(face.indices().len()).or_insert(0) += 1;
+    }
+    let sides: usize = histogram.iter().map(|(sides, count)| sides * count).sum();
+    let actual = (polyhedron.vertices().len(), sides / 2, polyhedron.faces().len());
+    assert_eq!(actual, sizes, "sizes of {}", notation);
+    let expected: BTreeMap<usize, usize> = face_sides.iter().cloned().collect();
+    assert_eq!(histogram, expected, "face sides of {}", notation);
+}
+
+#[test]
+fn join_ortho_expand() {
+    check("jC", (14, 24, 12), &[(4, 12)]);
+    check("oC", (26, 48, 24), &[(4, 24)]);
+    check("eC", (24, 48, 26), &[(3, 8), (4, 18)]);
+}
+
+#[test]
+fn gyro_snub() {
+    check("gC", (38, 60, 24), &[(5, 24)]);
+    check("g'C", (38, 60, 24), &[(5, 24)]);
+    check("sC", (24, 60, 38), &[(3, 32), (4, 6)]);
+    check("s'D", (60, 150, 92), &[(3, 80), (5, 12)]);
+
+    let right = "sC".parse::<Notation>().unwrap().build();
+    let left = "s'C".parse::<Notation>().unwrap().build();
+    assert_eq!(right.chirality(), Some(Chirality::Right));
+    assert_eq!(left.chirality(), Some(Chirality::Left));
+}
+
+#[test]
+fn bevel_meta() {
+    check("bC", (48, 72, 26), &[(4, 12), (6, 8), (8, 6)]);
+    check("mC", (26, 72, 48), &[(3, 48)]);
+}
+
+#[test]
+fn needle_zip() {
+    check("n5D", (12, 30, 20), &[(3, 20)]);
+    check("n4kC", (30, 60, 32), &[(3, 24), (6, 8)]);
+    check("zC", (24, 36, 14), &[(4, 6), (6, 8)]);
+    check("z5P5", (15, 25, 12), &[(4, 10), (5, 2)]);
+}
+
+#[test]
+fn propeller_reflect() {
+    check("pC", (32, 60, 30), &[(4, 30)]);
+    check("p'C", (32, 60, 30), &[(4, 30)]);
+    check("rC", (8, 12, 6), &[(4, 6)]);
+    check("rsC", (24, 60, 38), &[(3, 32), (4, 6)]);
+
+    let reflected = "rsC".parse::<Notation>().unwrap().build();
+    assert_eq!(reflected.chirality(), Some(Chirality::Left));
+}
+
+#[test]
+fn chamfer() {
+    check("cC", (32, 48, 18), &[(4, 6), (6, 12)]);
+    check("c(0.3)D", (80, 120, 42), &[(5, 12), (6, 30)]);
+}
+
+#[test]
+fn whirl() {
+    check("wC", (56, 84, 30), &[(4, 6), (6, 24)]);
+    check("w(0.5)D", (140, 210, 72), &[(5, 12), (6, 60)]);
+}
+
+#[test]
+fn loft_lace_stake_quinto_joined_lace_cross() {
+    check("lC", (32, 60, 30), &[(4, 30)]);
+    check("LC", (32, 84, 54), &[(3, 48), (4, 6)]);
+    check("KC", (38, 84, 48), &[(3, 24), (4, 24)]);
+    check("qC", (44, 72, 30), &[(4, 6), (5, 24)]);
+    check("L0C", (32, 72, 42), &[(3, 24), (4, 18)]);
+    check("xC", (50, 120, 72), &[(3, 48), (4, 24)]);
+}
+
+#[test]
+fn goldberg_coxeter() {
+    check("u3C", (56, 108, 54), &[(4, 54)]);
+    check("c(2,1)C", (32, 60, 30), &[(4, 30)]);
+    check("u3I", (92, 270, 180), &[(3, 180)]);
+    check("c(2,1)I", (72, 210, 140), &[(3, 140)]);
+}
+
+#[test]
+fn inset_extrude() {
+    check("iC", (32, 60, 30), &[(4, 30)]);
+    check("i4(0.3,-0.1)P5", (30, 55, 27), &[(4, 25), (5, 2)]);
+    check("E(0.2)C", (32, 60, 30), &[(4, 30)]);
+    check("E4(0.2,0.5)P5", (30, 55, 27), &[(4, 25), (5, 2)]);
+}
+
+#[test]
+fn face_selectors() {
+    check("k{sides=4}P5", (15, 35, 22), &[(3, 20), (5, 2)]);
+    check("k{class=0}aC", (20, 48, 30), &[(3, 24), (4, 6)]);
+    check("k{class=1}aC", (18, 48, 32), &[(3, 32)]);
+    check("k{area=1..}P5", (14, 33, 21), &[(3, 18), (4, 3)]);
+    check("k{normal=[0,0,1]<10}C", (9, 16, 9), &[(3, 4), (4, 5)]);
+    check("k{index=[0,1]}C", (10, 20, 12), &[(3, 8), (4, 4)]);
+    check("i{seed=[0]}C", (12, 20, 10), &[(4, 10)]);
+}
+
+#[test]
+fn vertex_and_edge_selectors() {
+    check("t3kI", (72, 150, 80), &[(3, 20), (4, 60)]);
+    check("t10kI", (140, 210, 72), &[(5, 60), (10, 12)]);
+    check("t(0.25)C", (24, 36, 14), &[(3, 8), (8, 6)]);
+    check("n4kC", (30, 60, 32), &[(3, 24), (6, 8)]);
+    check("c{0,4}aC", (60, 96, 38), &[(3, 8), (4, 6), (6, 24)]);
+    check("c{3,3}aC", (12, 24, 14), &[(3, 8), (4, 6)]);
+}
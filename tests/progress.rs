@@ -0,0 +1,214 @@
+//! Checks the order in which a build reports its progress, and that a cancelled build stops part
+//! way through an operator.
+
+use polyhedrator::{
+    BuildError, BuildOptions, Builder, CancellationToken, ConwayOperator, Counts, CustomOperator,
+    FaceKey, Notation, Observer, Operator, Polyhedron, SizeLimit, VertexKey,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Event {
+    Started(usize, Operator),
+    Face(usize, usize),
+    Finished(usize, Operator, usize),
+}
+
+/// Records each event, cancelling the token once the given event is recorded.
+struct Recorder {
+    events: Rc<RefCell<Vec<Event>>>,
+    cancel_at: Option<Event>,
+    token: CancellationToken,
+}
+
+impl Recorder {
+    fn record(&mut self, event: Event) {
+        if self.cancel_at.as_ref() == Some(&event) {
+            self.token.cancel();
+        }
+        self.events.borrow_mut().push(event);
+    }
+}
+
+impl Observer for Recorder {
+    fn operator_started(&mut self, index: usize, operator: &Operator) {
+        self.record(Event::Started(index, operator.clone()));
+    }
+
+    fn face_built(&mut self, built: usize, total: usize) {
+        self.record(Event::Face(built, total));
+    }
+
+    fn operator_finished(&mut self, index: usize, operator: &Operator, polyhedron: &Polyhedron) {
+        self.record(Event::Finished(index, operator.clone(), polyhedron.faces().len()));
+    }
+}
+
+/// Builds the notation, cancelling once the given event is recorded, and returns the result
+/// along with every event recorded.
+fn build(notation: &str, cancel_at: Option<Event>) -> (Result<Polyhedron, BuildError>, Vec<Event>) {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let token = CancellationToken::new();
+    let recorder = Recorder {
+        events: events.clone(),
+        cancel_at,
+        token: token.clone(),
+    };
+    let notation: Notation = notation.parse().unwrap();
+    let result = notation.build_observed(BuildOptions::default(), recorder, &token);
+    let events = events.borrow().clone();
+    (result, events)
+}
+
+/// The events of an operator assembling its faces once, as most operators do.
+fn single_pass(index: usize, operator: &Operator, faces: usize) -> Vec<Event> {
+    let mut events = vec![Event::Started(index, operator.clone())];
+    events.extend((1..=faces).map(|built| Event::Face(built, faces)));
+    events.push(Event::Finished(index, operator.clone(), faces));
+    events
+}
+
+#[test]
+fn event_order() {
+    let (result, events) = build("dkC", None);
+    assert_eq!(result.unwrap().faces().len(), 14);
+    let operators = "dkC".parse::<Notation>().unwrap().operators().to_vec();
+    let mut expected = single_pass(0, &operators[1], 24);
+    expected.extend(single_pass(1, &operators[0], 14));
+    assert_eq!(events, expected);
+}
+
+#[test]
+fn event_order_of_several_passes() {
+    // Goldberg-Coxeter divides pentagons into triangles, and then divides those triangles
+    let (result, events) = build("u3D", None);
+    assert_eq!(result.unwrap().faces().len(), 540);
+    let operator = "u3D".parse::<Notation>().unwrap().operators()[0].clone();
+    assert_eq!(events.first(), Some(&Event::Started(0, operator.clone())));
+    assert_eq!(events.last(), Some(&Event::Finished(0, operator, 540)));
+
+    let mut passes = Vec::new();
+    let mut expected_built = 1;
+    for event in &events[1..events.len() - 1] {
+        match event {
+            &Event::Face(built, total) => {
+                assert_eq!(built, expected_built, "{:?}", events);
+                expected_built = if built == total { 1 } else { built + 1 };
+                if built == total {
+                    passes.push(total);
+                }
+            },
+            event => panic!("unexpected {:?}", event),
+        }
+    }
+    assert_eq!(expected_built, 1);
+    assert_eq!(passes, vec![60, 540]);
+}
+
+#[test]
+fn cancelled_mid_build() {
+    let cases = [
+        // Part way through the second operator
+        ("dkC", Event::Face(10, 14)),
+        // Before a left-handed operator reflects its result back
+        ("s'C", Event::Face(3, 38)),
+        // During the first of several passes
+        ("u3D", Event::Face(5, 60)),
+        // Between operators
+        ("dkC", Event::Finished(0, "kC".parse::<Notation>().unwrap().operators()[0].clone(), 24)),
+    ];
+    for (notation, cancel_at) in cases.iter() {
+        let (result, events) = build(notation, Some(cancel_at.clone()));
+        assert!(matches!(result, Err(BuildError::Cancelled)), "{}", notation);
+        // Nothing is reported once the build is cancelled
+        assert_eq!(events.last(), Some(cancel_at), "{}", notation);
+        assert_eq!(events.iter().filter(|event| *event == cancel_at).count(), 1);
+    }
+}
+
+/// Copies each face of the polyhedron, cancelling the token after copying the first few.
+#[derive(Debug)]
+struct CancellingCopy {
+    token: CancellationToken,
+}
+
+impl ConwayOperator for CancellingCopy {
+    fn apply(&self, polyhedron: &Polyhedron, builder: &mut Builder) {
+        for (i, vertex) in polyhedron.vertices().iter().enumerate() {
+            builder.add_vertex(VertexKey::Seed(i as u32), *vertex);
+        }
+        for (i, face) in polyhedron.faces().iter().enumerate() {
+            if i == 2 {
+                self.token.cancel();
+            }
+            let mut previous = *face.indices().last().unwrap();
+            for &index in face.indices() {
+                let (source, destination) = (VertexKey::Seed(previous), VertexKey::Seed(index));
+                builder.add_flag(FaceKey::Seed(i as u32), source, destination);
+                previous = index;
+            }
+        }
+    }
+
+    fn transform_counts(&self, counts: &Counts) -> Counts {
+        counts.clone()
+    }
+}
+
+#[test]
+fn cancelled_while_adding_flags() {
+    // The operator runs to the end, but its builder ignores what it adds once cancelled, and
+    // reports no faces
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let token = CancellationToken::new();
+    let recorder = Recorder {
+        events: events.clone(),
+        cancel_at: None,
+        token: token.clone(),
+    };
+    let operator = CancellingCopy {
+        token: token.clone(),
+    };
+    let operator = Operator::Custom(CustomOperator::new('H', operator));
+    let seed = "C".parse::<Notation>().unwrap().build();
+    let operators = vec![operator.clone()];
+    let result = seed.apply_iter_observed(operators, SizeLimit::default(), recorder, &token);
+    assert!(matches!(result, Err(BuildError::Cancelled)));
+    assert_eq!(*events.borrow(), vec![Event::Started(0, operator)]);
+}
+
+#[test]
+fn unobserved_builds_ignore_tokens() {
+    // Only the build given a token stops when it is cancelled
+    let token = CancellationToken::new();
+    token.cancel();
+    let notation: Notation = "dkC".parse().unwrap();
+    assert!(matches!(
+        notation.build_observed(BuildOptions::default(), (), &token),
+        Err(BuildError::Cancelled)
+    ));
+    assert_eq!(notation.build().faces().len(), 14);
+}
+
+/// Panics while building, as an operator with a bug might.
+#[derive(Debug)]
+struct Panicking;
+
+impl ConwayOperator for Panicking {
+    fn apply(&self, _polyhedron: &Polyhedron, _builder: &mut Builder) {
+        panic!("operator failed");
+    }
+
+    fn transform_counts(&self, counts: &Counts) -> Counts {
+        counts.clone()
+    }
+}
+
+#[test]
+#[should_panic(expected = "operator failed")]
+fn panics_are_not_cancellation() {
+    let operators = vec![Operator::Custom(CustomOperator::new('H', Panicking))];
+    let seed = "C".parse::<Notation>().unwrap().build();
+    let _ = seed.apply_iter_observed(operators, SizeLimit::default(), (), &CancellationToken::new());
+}
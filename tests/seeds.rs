@@ -0,0 +1,22 @@
+//! Checks the counts of each seed against the polyhedron it builds.
+
+use polyhedrator::seeds::{Platonic, Seed};
+use polyhedrator::Counts;
+
+#[test]
+fn counts() {
+    let mut seeds: Vec<Seed> = Platonic::all().iter().cloned().map(Seed::Platonic).collect();
+    for sides in 3..=8 {
+        seeds.extend(vec![Seed::Prism(sides), Seed::Antiprism(sides), Seed::Pyramid(sides)]);
+    }
+    for seed in seeds {
+        assert_eq!(seed.counts(), Counts::of(&seed.polyhedron(1.0)), "{}", seed);
+    }
+}
+
+#[test]
+fn symbols() {
+    assert_eq!(<&str>::from(Seed::Platonic(Platonic::Cube)), "C");
+    assert_eq!(<&str>::from(Seed::Antiprism(7)), "A");
+    assert_eq!(Seed::Pyramid(4).to_string(), "Pyramid (4)");
+}
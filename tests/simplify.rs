@@ -0,0 +1,100 @@
+//! Checks each identity the simplifier uses, the rewrites it reports, and that simplified notation
+//! builds a polyhedron of the same size.
+
+use polyhedrator::{Counts, Notation};
+
+/// Operators matching a single identity, the identity, and the operators they are rewritten to.
+const IDENTITIES: &[(&str, &str, &str)] = &[
+    ("dkd", "dkd = t", "t"),
+    ("dtd", "dtd = k", "k"),
+    ("dd", "dd = identity", ""),
+    ("rr", "rr = identity", ""),
+    ("aa", "aa = e", "e"),
+    ("jj", "jj = o", "o"),
+    ("ta", "ta = b", "b"),
+    ("kj", "kj = m", "m"),
+    ("ad", "ad = a", "a"),
+    ("jd", "jd = j", "j"),
+    ("ed", "ed = e", "e"),
+    ("od", "od = o", "o"),
+    ("bd", "bd = b", "b"),
+    ("md", "md = m", "m"),
+    ("g'd", "gd = g", "g'"),
+    ("sd", "sd = s", "s"),
+    ("da", "da = j", "j"),
+    ("dj", "dj = a", "a"),
+    ("de", "de = o", "o"),
+    ("do", "do = e", "e"),
+    ("db", "db = m", "m"),
+    ("dm", "dm = b", "b"),
+    ("dg", "dg = s", "s"),
+    ("ds'", "ds = g", "g'"),
+    ("dk", "dk = z", "z"),
+    ("kd", "kd = n", "n"),
+    ("dz", "dz = k", "k"),
+    ("zd", "zd = t", "t"),
+    ("dn", "dn = t", "t"),
+    ("nd", "nd = k", "k"),
+    ("dt", "dt = n", "n"),
+    ("td", "td = z", "z"),
+];
+
+/// Builds both notations and checks that they have the same counts.
+fn check_sizes(original: &Notation, simplified: &Notation) {
+    let original_counts = Counts::of(&original.build());
+    let simplified_counts = Counts::of(&simplified.build());
+    assert_eq!(simplified_counts, original_counts, "{} simplified to {}", original, simplified);
+}
+
+#[test]
+fn identities() {
+    for (operators, identity, replacement) in IDENTITIES {
+        for seed in &["C", "D", "A5"] {
+            let notation: Notation = format!("{}{}", operators, seed).parse().unwrap();
+            let (simplified, rewrites) = notation.simplify();
+            assert_eq!(simplified.to_string(), format!("{}{}", replacement, seed));
+            assert_eq!(rewrites.len(), 1, "{}", notation);
+            assert_eq!(rewrites[0].identity(), *identity);
+            assert_eq!(rewrites[0].position(), 0);
+            assert_eq!(rewrites[0].before(), notation.operators());
+            assert_eq!(rewrites[0].after(), simplified.operators());
+            check_sizes(&notation, &simplified);
+        }
+    }
+}
+
+#[test]
+fn rewrite_positions() {
+    let notation: Notation = "etdaaC".parse().unwrap();
+    let (simplified, rewrites) = notation.simplify();
+    assert_eq!(simplified.to_string(), "ezeC");
+    let trace: Vec<String> = rewrites.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        trace,
+        vec![
+            "td -> z at position 1, since td = z",
+            "aa -> e at position 2, since aa = e",
+        ]
+    );
+    check_sizes(&notation, &simplified);
+
+    // Each rewrite may complete another with the operators before it
+    let notation: Notation = "ddkddI".parse().unwrap();
+    let (simplified, rewrites) = notation.simplify();
+    assert_eq!(simplified.to_string(), "kI");
+    let steps: Vec<(&str, usize)> =
+        rewrites.iter().map(|rewrite| (rewrite.identity(), rewrite.position())).collect();
+    assert_eq!(steps, vec![("dd = identity", 0), ("kd = n", 0), ("nd = k", 0)]);
+    check_sizes(&notation, &simplified);
+}
+
+#[test]
+fn parameters_are_kept() {
+    // Operators with parameters beyond a side count take no part in identities
+    for value in &["dk(0.2)dC", "dt(0.25)dC", "dk{class=0}dC", "c(0.3)c(0.3)C"] {
+        let notation: Notation = value.parse().unwrap();
+        let (simplified, rewrites) = notation.simplify();
+        assert_eq!(simplified, notation);
+        assert!(rewrites.is_empty(), "{}", value);
+    }
+}